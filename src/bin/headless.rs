@@ -0,0 +1,55 @@
+use game::{
+    create_logger,
+    headless::{
+        run,
+        Options,
+    },
+};
+use log::LevelFilter;
+use std::{
+    env,
+    path::PathBuf,
+    process,
+};
+
+const USAGE: &str = "Usage: headless [--config <game_config.ron>] [--waves <waves.ron>] [--seed <u64>] [--wave-count <n>] [--towers <commands.ron>] [--replay <replay.ron>] [--record <replay.ron>] [--check-config]";
+
+fn parse_args() -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("Missing value for {}", arg));
+        match arg.as_str() {
+            "--config" => options.config = Some(PathBuf::from(value()?)),
+            "--waves" => options.waves = Some(PathBuf::from(value()?)),
+            "--seed" => options.seed = Some(value()?.parse().map_err(|e| format!("Bad seed: {}", e))?),
+            "--wave-count" => options.wave_count = Some(value()?.parse().map_err(|e| format!("Bad wave count: {}", e))?),
+            "--towers" => options.towers = Some(PathBuf::from(value()?)),
+            "--replay" => options.replay = Some(PathBuf::from(value()?)),
+            "--record" => options.record = Some(PathBuf::from(value()?)),
+            "--check-config" => options.check_config = true,
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                process::exit(0);
+            },
+            _ => return Err(format!("Unknown argument {}", arg)),
+        }
+    }
+
+    Ok(options)
+}
+
+fn main() -> amethyst::Result<()> {
+    let options = match parse_args() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}\n{}", e, USAGE);
+            process::exit(1);
+        },
+    };
+
+    // Only the report at the end is wanted on stdout
+    create_logger(LevelFilter::Warn);
+    run(options).map(|_| ())
+}
\ No newline at end of file
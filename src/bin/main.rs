@@ -1,5 +1,44 @@
-use game::run;
+use game::{
+    run,
+    Options,
+};
+use std::{
+    env,
+    path::PathBuf,
+    process,
+};
+
+const USAGE: &str = "Usage: game [--replay <replay.ron>] [--record <replay.ron>] [--check-config]";
+
+fn parse_args() -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("Missing value for {}", arg));
+        match arg.as_str() {
+            "--replay" => options.replay = Some(PathBuf::from(value()?)),
+            "--record" => options.record = Some(PathBuf::from(value()?)),
+            "--check-config" => options.check_config = true,
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                process::exit(0);
+            },
+            _ => return Err(format!("Unknown argument {}", arg)),
+        }
+    }
+
+    Ok(options)
+}
 
 fn main() -> amethyst::Result<()> {
-    run()
+    let options = match parse_args() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}\n{}", e, USAGE);
+            process::exit(1);
+        },
+    };
+
+    run(options)
 }
@@ -0,0 +1,119 @@
+use amethyst::{
+    config::Config,
+    utils::application_root_dir,
+};
+use game::{
+    components::Map,
+    config::{
+        validate,
+        Game as GameConfig,
+    },
+    util::map_export::{
+        map_image,
+        MapSummary,
+    },
+};
+use ron::ser::{
+    to_string_pretty,
+    PrettyConfig,
+};
+use std::{
+    env,
+    fs,
+    path::PathBuf,
+    process,
+};
+
+const USAGE: &str = "Usage: map_export [--config <game_config.ron>] [--seed <u64>] [--ascii <file>] [--png <file>] [--ron <file>]\nWith no outputs given the ASCII map is written to stdout";
+
+#[derive(Default)]
+struct Options {
+    config: Option<PathBuf>,
+    seed: Option<u64>,
+    ascii: Option<PathBuf>,
+    png: Option<PathBuf>,
+    ron: Option<PathBuf>,
+}
+
+fn parse_args() -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("Missing value for {}", arg));
+        match arg.as_str() {
+            "--config" => options.config = Some(PathBuf::from(value()?)),
+            "--seed" => options.seed = Some(value()?.parse().map_err(|e| format!("Bad seed: {}", e))?),
+            "--ascii" => options.ascii = Some(PathBuf::from(value()?)),
+            "--png" => options.png = Some(PathBuf::from(value()?)),
+            "--ron" => options.ron = Some(PathBuf::from(value()?)),
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                process::exit(0);
+            },
+            _ => return Err(format!("Unknown argument {}", arg)),
+        }
+    }
+
+    Ok(options)
+}
+
+fn export(options: Options) -> Result<(), String> {
+    let config_path = match options.config {
+        Some(path) => path,
+        None => application_root_dir()
+            .map_err(|e| format!("Failed to find the application root: {}", e))?
+            .join("config")
+            .join("game_config.ron"),
+    };
+    let game_config = GameConfig::load_no_fallback(&config_path)
+        .map_err(|e| format!("Failed to load {:?}: {}", config_path, e))?;
+    let problems = game_config.validate();
+    if !problems.is_empty() {
+        return Err(validate::report(&problems));
+    }
+    let mut map_config = game_config.map;
+    if let Some(seed) = options.seed {
+        map_config.seed = Some(seed);
+    }
+
+    let mut map = Map::new(map_config.width, map_config.height);
+    map.generate(&map_config);
+    eprintln!("Generated {}x{} map with seed {}", map.width(), map.height(), map.seed());
+
+    let no_outputs = options.ascii.is_none() && options.png.is_none() && options.ron.is_none();
+    if no_outputs {
+        println!("{}", map.to_ascii());
+    }
+
+    if let Some(path) = options.ascii {
+        fs::write(&path, map.to_ascii()).map_err(|e| format!("Failed to write {:?}: {}", path, e))?;
+    }
+
+    if let Some(path) = options.png {
+        map_image(&map).save(&path).map_err(|e| format!("Failed to write {:?}: {}", path, e))?;
+    }
+
+    if let Some(path) = options.ron {
+        let summary = to_string_pretty(&MapSummary::new(&map), PrettyConfig::default())
+            .map_err(|e| format!("Failed to serialize map: {}", e))?;
+        fs::write(&path, summary).map_err(|e| format!("Failed to write {:?}: {}", path, e))?;
+    }
+
+    Ok(())
+}
+
+fn main() {
+    let options = match parse_args() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}\n{}", e, USAGE);
+            process::exit(1);
+        },
+    };
+
+    if let Err(e) = export(options) {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
\ No newline at end of file
@@ -1,6 +1,7 @@
 use amethyst::ecs::{Component, DenseVecStorage};
+use serde::{Deserialize, Serialize};
 
-#[derive(Default)]
+#[derive(Default, Deserialize, Serialize)]
 pub struct Age {
     pub age: f32,
     pub max_age: Option<f32>,
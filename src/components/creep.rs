@@ -1,8 +1,57 @@
-use amethyst::ecs::{Component, NullStorage};
+use amethyst::ecs::{Component, DenseVecStorage};
+use crate::config::creep::CreepArchetype;
 
 #[derive(Default)]
-pub struct Creep;
+pub struct Creep {
+    /// Name of the CreepArchetype in the creep config
+    pub archetype: String,
+    /// Copied from the archetype as they're needed for every hit
+    pub armour: f32,
+    pub size: f32,
+}
+
+impl Creep {
+    pub fn new(archetype: &CreepArchetype) -> Self {
+        Self {
+            archetype: archetype.name.clone(),
+            armour: archetype.armour,
+            size: archetype.size,
+        }
+    }
+
+    /// Damage taken from a hit of the given damage after armour
+    pub fn damage_taken(&self, damage: f32) -> f32 {
+        (damage - self.armour).max(0.)
+    }
+}
 
 impl Component for Creep {
-    type Storage = NullStorage<Self>;
+    type Storage = DenseVecStorage<Self>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn creep(armour: f32) -> Creep {
+        Creep::new(&CreepArchetype {
+            armour,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn unarmoured_creeps_take_full_damage() {
+        assert_eq!(creep(0.).damage_taken(3.), 3.);
+    }
+
+    #[test]
+    fn armour_is_taken_off_each_hit() {
+        assert_eq!(creep(1.).damage_taken(3.), 2.);
+    }
+
+    #[test]
+    fn hits_weaker_than_the_armour_do_nothing() {
+        assert_eq!(creep(5.).damage_taken(3.), 0.);
+    }
 }
\ No newline at end of file
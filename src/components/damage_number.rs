@@ -0,0 +1,19 @@
+use amethyst::ecs::{Component, DenseVecStorage};
+
+/// Damage dealt to a creep, drawn rising and fading over the entity's Age
+#[derive(Debug, Clone, Copy)]
+pub struct DamageNumber {
+    pub damage: f32,
+}
+
+impl DamageNumber {
+    pub fn new(damage: f32) -> Self {
+        Self {
+            damage,
+        }
+    }
+}
+
+impl Component for DamageNumber {
+    type Storage = DenseVecStorage<Self>;
+}
\ No newline at end of file
@@ -0,0 +1,26 @@
+use amethyst::ecs::{Component, DenseVecStorage};
+use crate::resources::SimClock;
+
+/// Seconds of game time a killed creep lingers for, about as long as the death animations
+pub const DEATH_SECONDS: f32 = 0.6;
+
+/// Marks a killed creep that is playing its death animation. Murder deletes it on
+/// end_tick however far the animation got, so a game plays out the same with or
+/// without rendering
+#[derive(Debug, Clone, Copy)]
+pub struct Dying {
+    /// SimClock::tick to delete the entity on
+    pub end_tick: u64,
+}
+
+impl Dying {
+    pub fn new(clock: &SimClock) -> Self {
+        Self {
+            end_tick: clock.tick + (DEATH_SECONDS / clock.tick_seconds).ceil() as u64,
+        }
+    }
+}
+
+impl Component for Dying {
+    type Storage = DenseVecStorage<Self>;
+}
\ No newline at end of file
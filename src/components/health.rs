@@ -0,0 +1,25 @@
+use amethyst::ecs::{Component, DenseVecStorage};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub struct Health {
+    pub health: f32,
+    pub max_health: f32,
+}
+
+impl Health {
+    pub fn new(max_health: f32) -> Self {
+        Self {
+            health: max_health,
+            max_health,
+        }
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.health <= 0.
+    }
+}
+
+impl Component for Health {
+    type Storage = DenseVecStorage<Self>;
+}
\ No newline at end of file
@@ -11,12 +11,13 @@ use amethyst::{
 use rand::{
 	self,
 	Rng,
-	rngs::ThreadRng,
+	SeedableRng,
 	seq::SliceRandom,
 };
+//Unlike StdRng its output is the same across rand versions, so seeds keep their layouts
+use rand_pcg::Pcg64Mcg;
 use ndarray::{
 	Array2,
-	Axis,
 	s,
 };
 use crate::{
@@ -29,7 +30,14 @@ use crate::{
 	},
 	util::screen_to_iso,
 };
-use std::slice::IterMut;
+use pathfinding::prelude::{
+	bfs,
+	dijkstra_all,
+};
+use std::{
+	fmt,
+	slice::IterMut,
+};
 
 union Transmute<T: Copy, U: Copy> {
 		from: T,
@@ -76,8 +84,9 @@ const NEIGHBOURS_WITH_COST: [(Vector2<i8>, i32); 8] = [
 ];
 
 
+/// What a map cell was generated as. The ids tell rooms and corridors apart
 #[derive(Debug, Copy, Clone, PartialEq)]
-enum TileState {
+pub enum TileState {
 	Wall,
 	Door,
 	Corridor(u32),
@@ -191,11 +200,50 @@ impl PartialEq for Door {
 	}
 }
 
+///Reasons a tower can't be placed in a cell
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PlacementError {
+	OutOfBounds,
+	Wall,
+	Occupied,
+	Entrance,
+	Exit,
+	TowerLimit,
+	BlocksPath,
+	CantAfford,
+	CreepInTheWay,
+}
+
+impl fmt::Display for PlacementError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let reason = match self {
+			PlacementError::OutOfBounds => "outside the map",
+			PlacementError::Wall => "can't build on walls",
+			PlacementError::Occupied => "there's already a tower there",
+			PlacementError::Entrance => "can't build on an entrance",
+			PlacementError::Exit => "can't build on the exit",
+			PlacementError::TowerLimit => "tower limit reached",
+			PlacementError::BlocksPath => "creeps wouldn't be able to reach the exit",
+			PlacementError::CantAfford => "not enough gold",
+			PlacementError::CreepInTheWay => "a creep is in the way",
+		};
+		write!(f, "{}", reason)
+	}
+}
 
 pub struct Map {
 	width: usize,
 	height: usize,
+	//The seed used by the last call to generate
+	seed: u64,
+	//Bumped whenever the traversable cells change so cached navigation data can be rebuilt
+	version: u32,
 	state: Array2<TileState>,
+	//Cells with towers in, these can't be walked through
+	occupied: Array2<bool>,
+	//Creeps enter at the entrances and try to reach the exit
+	entrances: Vec<(usize, usize)>,
+	exit: Option<(usize, usize)>,
 	//These are only for early debugging visualisation
 	rooms: Vec<MapObject>,
 	doors: Vec<MapObject>,
@@ -215,7 +263,12 @@ impl Map {
 		Self {
 			width,
 			height,
+			seed: 0,
+			version: 0,
 			state: Array2::default((width, height)),
+			occupied: Array2::default((width, height)),
+			entrances: Vec::new(),
+			exit: None,
 			rooms: Vec::new(),
 			doors: Vec::new(),
 			corridors: Vec::new(),
@@ -226,11 +279,60 @@ impl Map {
 
 	pub fn width(&self) -> u32 { self.width as u32}
 	pub fn height(&self) -> u32 { self.height as u32 }
+	pub fn seed(&self) -> u64 { self.seed }
+	pub fn version(&self) -> u32 { self.version }
+	pub fn entrances(&self) -> &[(usize, usize)] { &self.entrances }
+	pub fn exit(&self) -> Option<(usize, usize)> { self.exit }
 	pub fn is_wall(&self, x: u32, y: u32) -> bool {
 		let i = (x as usize, y as usize);
 		self.state[i] == Wall
 	}
 
+	pub fn is_occupied(&self, cell: (usize, usize)) -> bool {
+		self.occupied.get(cell).cloned().unwrap_or(false)
+	}
+
+	pub fn set_occupied(&mut self, cell: (usize, usize), occupied: bool) {
+		if self.occupied[cell] != occupied {
+			self.occupied[cell] = occupied;
+			self.version += 1;
+		}
+	}
+
+	pub fn can_place_tower(&self, cell: (usize, usize)) -> Result<(), PlacementError> {
+		if cell.0 >= self.width || cell.1 >= self.height {
+			Err(PlacementError::OutOfBounds)
+		} else if self.state[cell] == Wall {
+			Err(PlacementError::Wall)
+		} else if self.occupied[cell] {
+			Err(PlacementError::Occupied)
+		} else if self.entrances.contains(&cell) {
+			Err(PlacementError::Entrance)
+		} else if self.exit == Some(cell) {
+			Err(PlacementError::Exit)
+		} else if !self.exit_reachable_without(cell) {
+			Err(PlacementError::BlocksPath)
+		} else {
+			Ok(())
+		}
+	}
+
+	///Checks every entrance could still reach the exit if blocked was occupied
+	pub fn exit_reachable_without(&self, blocked: (usize, usize)) -> bool {
+		let exit = match self.exit {
+			Some(exit) => exit,
+			None => return true,
+		};
+
+		self.entrances.iter().all(|entrance| {
+			bfs(
+				entrance,
+				|&p| self.successors(p).into_iter().map(|(n, _)| n).filter(move |n| *n != blocked),
+				|p| *p == exit,
+			).is_some()
+		})
+	}
+
 	pub fn world_to_cell_index(&self, pos: Vector2<f32>) -> Option<(usize, usize)> {
 		let pos = screen_to_iso(pos);
 
@@ -340,25 +442,45 @@ impl Map {
 		self.walls.iter_mut()
 	}
 
-	fn print(&self) {
-        for x in self.state.axis_iter(Axis(0)).rev() {
-            println!();
-            for y in x.iter().rev() {
-                match y {
-                    Room(_) => print!(" "),
-                    Corridor(_) => print!(" "),
-                    Door => print!("d"),
-                    _ => print!("#"),
-                }
-            }
-        }
-		println!();
+	pub fn tile(&self, cell: (usize, usize)) -> Option<TileState> {
+		self.state.get(cell).cloned()
+	}
+
+	///One line per x, flipped so it reads the same way round as the rendered map
+	pub fn to_ascii(&self) -> String {
+		let mut ascii = String::with_capacity((self.height + 1) * self.width);
+		for x in (0..self.width).rev() {
+			if x + 1 < self.width {
+				ascii.push('\n');
+			}
+			for y in (0..self.height).rev() {
+				let cell = (x, y);
+				ascii.push(if self.entrances.contains(&cell) {
+					'E'
+				} else if self.exit == Some(cell) {
+					'X'
+				} else {
+					match self.state[cell] {
+						Room(_) => ' ',
+						Corridor(_) => ' ',
+						Door => 'd',
+						_ => '#',
+					}
+				});
+			}
+		}
+		ascii
 	}
 
 	fn reset_state(&mut self) {
 		for s in self.state.iter_mut() {
 			*s = Default::default();
 		}
+		for o in self.occupied.iter_mut() {
+			*o = false;
+		}
+		self.entrances.clear();
+		self.exit = None;
 		self.rooms.clear();
 		self.doors.clear();
 		self.corridors.clear();
@@ -366,7 +488,7 @@ impl Map {
 		self.walls.clear();
 	}
 
-	fn place_room(&mut self, room_size: &RoomConfig, entity_config: &MapEntityConfig, rng: &mut ThreadRng, room_id: u32) -> Result<(), ()> {
+	fn place_room(&mut self, room_size: &RoomConfig, entity_config: &MapEntityConfig, rng: &mut Pcg64Mcg, room_id: u32) -> Result<(), ()> {
 		let size_x = rng.gen_range(room_size.min_size.0 as usize, room_size.max_size.0 as usize);
 		let size_y = rng.gen_range(room_size.min_size.1 as usize, room_size.max_size.1 as usize);
 
@@ -405,7 +527,7 @@ impl Map {
 		Ok(())
 	}
 
-	fn fill_rooms(&mut self, config: &MapConfig, rng: &mut ThreadRng) {
+	fn fill_rooms(&mut self, config: &MapConfig, rng: &mut Pcg64Mcg) {
 		let room_sizes = &config.room_sizes;
 		for rs in room_sizes.iter() {
 			log::info!("Room Size: {}, ({}, {}) -> ({}, {})",
@@ -467,7 +589,7 @@ impl Map {
 		Some((ux, uy))
 	}
 
-	fn fill_maze_from(&mut self, start: (usize, usize), corridor_id: u32, neighbours4: &mut Vec<Vector2<i8>>, neighbours8: &Vec<Vector2<i8>>, config: &MapConfig, rng: &mut ThreadRng) {
+	fn fill_maze_from(&mut self, start: (usize, usize), corridor_id: u32, neighbours4: &mut Vec<Vector2<i8>>, neighbours8: &Vec<Vector2<i8>>, config: &MapConfig, rng: &mut Pcg64Mcg) {
 		let new_state = Corridor(corridor_id);
 		self.state[start] = new_state;
 
@@ -547,7 +669,7 @@ impl Map {
 		log::debug!("Corridor {} covers {} cells", corridor_id, covered_cells);
 	}
 
-	fn fill_maze(&mut self, config: &MapConfig, rng: &mut ThreadRng) {
+	fn fill_maze(&mut self, config: &MapConfig, rng: &mut Pcg64Mcg) {
 		let neighbours8 = NEIGHBOURS_8.to_vec();
 		let mut neighbours4 = NEIGHBOURS_4.to_vec();
 
@@ -601,7 +723,7 @@ impl Map {
 		None
 	}
 
-	fn add_doors(&mut self, config: &MapConfig, rng: &mut ThreadRng) {
+	fn add_doors(&mut self, config: &MapConfig, rng: &mut Pcg64Mcg) {
 		let mut possible_doors = Vec::new();
 
 		//Find all possible possible_doors
@@ -709,6 +831,71 @@ impl Map {
 		log::info!("Removed {} dead ends", removed);
 	}
 
+	///Path costs from start to every cell reachable from it, including start itself
+	fn distances_from(&self, start: (usize, usize)) -> Vec<((usize, usize), i32)> {
+		let mut res: Vec<_> = dijkstra_all(&start, |&p| self.successors(p))
+			.into_iter()
+			.map(|(p, (_, cost))| (p, cost))
+			.collect();
+		res.push((start, 0));
+		//HashMap order isn't stable so sort it to keep generation reproducible
+		res.sort();
+		res
+	}
+
+	fn place_endpoints(&mut self, config: &MapConfig, rng: &mut Pcg64Mcg) {
+		//Only use the largest connected region so the entrances are guaranteed to reach the exit
+		let mut visited: Array2<bool> = Array2::default((self.width, self.height));
+		let mut region = Vec::new();
+		for (i, s) in self.state.indexed_iter() {
+			if *s == Wall || visited[i] { continue; }
+			let reachable = self.distances_from(i);
+			for (p, _) in reachable.iter() {
+				visited[*p] = true;
+			}
+			if reachable.len() > region.len() {
+				region = reachable;
+			}
+		}
+
+		let start = match region.choose(rng) {
+			Some((start, _)) => *start,
+			None => {
+				log::warn!("Map has no open cells for the entrances and exit");
+				return;
+			},
+		};
+
+		let furthest = |distances: &[((usize, usize), i32)]| {
+			distances.iter().max_by_key(|(_, cost)| *cost).map(|(p, _)| *p).unwrap_or(start)
+		};
+
+		//The furthest cell from anywhere is at one end of the longest path through the region
+		let exit = furthest(&self.distances_from(start));
+		let mut from_exit = self.distances_from(exit);
+		self.exit = Some(exit);
+
+		//Entrances are picked furthest first as long as they aren't too close to each other
+		from_exit.sort_by_key(|(_, cost)| -cost);
+		let min_separation = (self.width.min(self.height) / 4).max(1) as f32;
+		for (p, _) in from_exit {
+			if self.entrances.len() >= config.entrance_count as usize { break; }
+			let separated = self.entrances.iter().all(|e| {
+				let dx = e.0 as f32 - p.0 as f32;
+				let dy = e.1 as f32 - p.1 as f32;
+				(dx * dx + dy * dy).sqrt() >= min_separation
+			});
+			if separated && p != exit {
+				self.entrances.push(p);
+			}
+		}
+
+		if self.entrances.len() < config.entrance_count as usize {
+			log::warn!("Only found room for {} of {} entrances", self.entrances.len(), config.entrance_count);
+		}
+		log::info!("Exit at {:?}, entrances at {:?}", exit, self.entrances);
+	}
+
 	fn fill_debug_vecs(&mut self, config: &MapEntityConfig) {
 		for ((x, y), s) in self.state.indexed_iter() {
 			match s {
@@ -790,10 +977,19 @@ impl Map {
 		log::info!("Greedy meshing reduced {} wall blocks to {} meshes", wall_count, self.walls.len());
 	}
 
+	///Generates the map using config.seed if there is one, otherwise a random seed
 	pub fn generate(&mut self, config: &MapConfig) {
+		let seed = config.seed.unwrap_or_else(rand::random);
+		self.generate_with_seed(config, seed);
+	}
+
+	pub fn generate_with_seed(&mut self, config: &MapConfig, seed: u64) {
 		self.reset_state();
 
-		let mut rng = rand::thread_rng();
+		log::info!("Generating map with seed {}", seed);
+		self.seed = seed;
+		self.version += 1;
+		let mut rng = Pcg64Mcg::seed_from_u64(seed);
 
 		//Place as many rooms as possible
 		self.fill_rooms(config, &mut rng);
@@ -807,11 +1003,14 @@ impl Map {
 		//Remove dead ends
 		self.remove_dead_ends();
 
+		//Pick where creeps come from and go to
+		self.place_endpoints(config, &mut rng);
+
 		self.fill_debug_vecs(&config.entity);
 
 		self.greedy_mesh_walls(&config.entity);
 
-		self.print();
+		log::debug!("Generated map:\n{}", self.to_ascii());
 	}
 
 	///Pretty janky, doesn't account for radius of the from point and always gives the center of a tile back
@@ -897,6 +1096,9 @@ impl Map {
 
 		for (n, cost) in NEIGHBOURS_WITH_COST.iter() {
 			if let Some(n) = self.resolve_neighbour((x, y), n) {
+				if self.occupied[n] {
+					continue;
+				}
 				match self.state[n] {
                     Wall => continue,
                     _ => res.push((n, *cost)),
@@ -938,4 +1140,160 @@ fn clamp(x: f32, y: f32, w: usize, h: usize) -> (usize, usize, bool) {
 	};
 
 	(x, y, clamped)
+}
+
+#[cfg(test)]
+impl Map {
+	///Builds a map from rows of text, x along each row and y down them.
+	///'#' is wall, ' ' corridor, 'E' an entrance and 'X' the exit
+	pub(crate) fn from_layout(rows: &[&str]) -> Self {
+		let mut map = Map::new(rows[0].len() as u32, rows.len() as u32);
+		for (y, row) in rows.iter().enumerate() {
+			for (x, c) in row.chars().enumerate() {
+				let cell = (x, y);
+				map.state[cell] = match c {
+					'#' => Wall,
+					_ => Corridor(0),
+				};
+				match c {
+					'E' => map.entrances.push(cell),
+					'X' => map.exit = Some(cell),
+					_ => {},
+				}
+			}
+		}
+		map
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::config::map::Room as RoomConfig;
+
+	fn test_config() -> MapConfig {
+		MapConfig {
+			width: 24,
+			height: 24,
+			room_place_max_iterations: 50,
+			corridor_turn_chance: 0.3,
+			superfluous_doors_per_room: 0.5,
+			room_sizes: vec!(RoomConfig {
+				name: "Small".to_string(),
+				frequency: 0.5,
+				min_size: (3, 3),
+				max_size: (7, 7),
+			}),
+			entrance_count: 2,
+			..Default::default()
+		}
+	}
+
+	fn generate(config: &MapConfig, seed: u64) -> Map {
+		let mut map = Map::new(config.width, config.height);
+		map.generate_with_seed(config, seed);
+		map
+	}
+
+	#[test]
+	fn same_seed_gives_same_layout() {
+		let config = test_config();
+		assert_eq!(generate(&config, 42).to_ascii(), generate(&config, 42).to_ascii());
+	}
+
+	#[test]
+	fn generate_uses_config_seed() {
+		let mut config = test_config();
+		config.seed = Some(42);
+		let mut map = Map::new(config.width, config.height);
+		map.generate(&config);
+		assert_eq!(map.seed(), 42);
+		assert_eq!(map.to_ascii(), generate(&config, 42).to_ascii());
+	}
+
+	#[test]
+	fn different_seeds_give_different_layouts() {
+		let config = test_config();
+		assert_ne!(generate(&config, 1).to_ascii(), generate(&config, 2).to_ascii());
+	}
+
+	#[test]
+	fn seed_12345_layout() {
+		let map = generate(&test_config(), 12345);
+		let expected = [
+			"########################",
+			"########################",
+			"###########     d ######",
+			"###########     #    ###",
+			"####     ##     ####d###",
+			"####     ##     #     ##",
+			"## d     ##     d     ##",
+			"## #     ##d#####     ##",
+			"## #     #    ###d##d###",
+			"## #    E# ## #   #  ###",
+			"## ####### ## #   d ####",
+			"##         #  #   #    #",
+			"## #######d# ##   # ## #",
+			"## #  d    # ####d#  # #",
+			"## # ##    #      ## # #",
+			"## # ##    ###### ## # #",
+			"## # ##    ######    # #",
+			"## # #######    d #### #",
+			"#  # #######    #    # #",
+			"# ## d    ##    ##d#d# #",
+			"# ## #    ##    #    #X#",
+			"# ## #    ##    d    # #",
+			"#E   ############    d #",
+			"########################",
+		].join("\n");
+		assert_eq!(map.to_ascii(), expected);
+		assert_eq!(map.exit(), Some((3, 1)));
+		assert_eq!(map.entrances(), &[(14, 15), (1, 22)]);
+	}
+
+	#[test]
+	fn blocking_the_only_route_cuts_off_the_exit() {
+		let map = Map::from_layout(&[
+			"#######",
+			"#E   X#",
+			"#######",
+		]);
+		assert!(!map.exit_reachable_without((3, 1)));
+		assert_eq!(map.can_place_tower((3, 1)), Err(PlacementError::BlocksPath));
+	}
+
+	#[test]
+	fn blocking_one_of_two_routes_keeps_the_exit_reachable() {
+		let map = Map::from_layout(&[
+			"#######",
+			"#     #",
+			"#E # X#",
+			"#     #",
+			"#######",
+		]);
+		assert!(map.exit_reachable_without((3, 1)));
+		assert_eq!(map.can_place_tower((3, 1)), Ok(()));
+	}
+
+	#[test]
+	fn every_entrance_has_to_reach_the_exit() {
+		let map = Map::from_layout(&[
+			"#########",
+			"#E  X  E#",
+			"#########",
+		]);
+		assert!(map.exit_reachable_without((1, 0)));
+		assert!(!map.exit_reachable_without((6, 1)));
+		assert!(!map.exit_reachable_without((2, 1)));
+	}
+
+	#[test]
+	fn exit_reachable_without_a_map_exit() {
+		let map = Map::from_layout(&[
+			"#####",
+			"#E  #",
+			"#####",
+		]);
+		assert!(map.exit_reachable_without((2, 1)));
+	}
 }
\ No newline at end of file
@@ -30,11 +30,26 @@ mod creep;
 pub use creep::Creep;
 
 mod tower;
-pub use tower::{ Tower, BulletTower };
+pub use tower::{
+    Tower,
+    TargetingMode,
+};
 
 mod projectile;
 pub use projectile::Projectile;
 
+mod health;
+pub use health::Health;
+
+mod damage_number;
+pub use damage_number::DamageNumber;
+
+mod dying;
+pub use dying::{Dying, DEATH_SECONDS};
+
+mod tick_position;
+pub use tick_position::TickPosition;
+
 /// This allows systems to be commented in and out without causing runtime errors
 pub fn register_components<S, T, E, X>(builder: ApplicationBuilder<S, T, E, X>) -> ApplicationBuilder<S, T, E, X>
 where
@@ -49,7 +64,10 @@ where
         .register::<Path>()
         .register::<Color>()
         .register::<Creep>()
-        .register::<BulletTower>()
         .register::<Tower>()
         .register::<Projectile>()
+        .register::<Health>()
+        .register::<DamageNumber>()
+        .register::<Dying>()
+        .register::<TickPosition>()
 }
\ No newline at end of file
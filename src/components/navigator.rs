@@ -2,13 +2,34 @@ use amethyst::{
     ecs::{Component, DenseVecStorage},
     core::math::Vector2,
 };
+use serde::{Deserialize, Serialize};
 use crate::components::PathNode;
 
 /// Navigator indicates a sprite that navigates the map
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Deserialize, Serialize)]
 pub struct Navigator {
     pub x: usize,
     pub y: usize,
+    /// The cell being moved towards
+    pub next: Option<(usize, usize)>,
+    /// Distance covered between x, y and next
+    pub progress: f32,
+}
+
+impl Navigator {
+    pub fn new(x: usize, y: usize) -> Self {
+        Self {
+            x,
+            y,
+            next: None,
+            progress: 0.,
+        }
+    }
+
+    /// True if the navigator is standing in or heading into cell, so a tower can't go there
+    pub fn is_in(&self, cell: (usize, usize)) -> bool {
+        (self.x, self.y) == cell || self.next == Some(cell)
+    }
 }
 
 impl Component for Navigator {
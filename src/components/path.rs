@@ -12,7 +12,7 @@ use pathfinding::prelude::{
     astar,
     absdiff,
 };
-use std::time::Instant;
+use serde::{Deserialize, Serialize};
 use crate::components::Map;
 
 const DIAGONAL_COST_1: i32 = 1;
@@ -42,7 +42,7 @@ fn distance(a: &PathNode, b: &PathNode, allow_diagonals: bool) -> f32 {
 }
 */
 
-#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Deserialize, Serialize)]
 pub struct PathNode {
     pub x: i32,
     pub y: i32,
@@ -67,7 +67,10 @@ pub struct Path {
     origin: PathNode,
     pub path: Option<(Vec<PathNode>, i32)>,
     pub path_i: Option<usize>,
-    pub start_time: Option<Instant>,
+    /// SimClock::elapsed when the navigator left the previous node
+    pub start_time: Option<f32>,
+    /// Map::version the path was found on
+    pub map_version: u32,
 }
 
 impl Path {
@@ -79,10 +82,39 @@ impl Path {
             path: None,
             path_i: None,
             start_time: None,
+            map_version: 0,
         }
     }
+
+    pub fn objective(&self) -> PathNode { self.objective }
+    pub fn origin(&self) -> PathNode { self.origin }
+
+    /// Throws away the current path so a new one is found from origin
+    pub fn replan(&mut self, origin: PathNode) {
+        self.origin = origin;
+        self.path = None;
+        self.path_i = None;
+        self.start_time = None;
+    }
+    /// Number of path nodes left to visit, None if there's no path
+    pub fn remaining(&self) -> Option<usize> {
+        match (&self.path, self.path_i) {
+            (Some((path, _)), Some(i)) => Some(path.len().saturating_sub(i)),
+            _ => None,
+        }
+    }
+
+    /// True once the navigator has reached the end of the path
+    pub fn is_complete(&self) -> bool {
+        match (&self.path, self.path_i) {
+            (Some((path, _)), Some(i)) => i >= path.len(),
+            _ => false,
+        }
+    }
+
     pub fn run(&mut self, map: &Map) {
         if self.path.is_none() {
+            self.map_version = map.version();
             let path = astar(
                 &self.origin,
                 |p| map.successors((p.x as usize, p.y as usize)).into_iter().map(|((x, y), cost)| (PathNode::new(x as i32, y as i32), cost)),
@@ -90,8 +122,7 @@ impl Path {
                 |p| *p == self.objective,
             );
 
-            if let Some(mut path) = path {
-                path.0.reverse();
+            if let Some(path) = path {
                 self.path = Some(path);
                 self.path_i = Some(0);
             } else {
@@ -1,8 +1,22 @@
-use amethyst::ecs::{ Component, NullStorage };
+use amethyst::ecs::{ Component, DenseVecStorage };
+use serde::{Deserialize, Serialize};
+use crate::config::tower::ProjectileKind;
 
-#[derive(Default)]
-pub struct Projectile;
+#[derive(Default, Deserialize, Serialize)]
+pub struct Projectile {
+    pub damage: f32,
+    pub kind: ProjectileKind,
+}
+
+impl Projectile {
+    pub fn new(damage: f32, kind: ProjectileKind) -> Self {
+        Self {
+            damage,
+            kind,
+        }
+    }
+}
 
 impl Component for Projectile {
-    type Storage = NullStorage<Self>;
+    type Storage = DenseVecStorage<Self>;
 }
\ No newline at end of file
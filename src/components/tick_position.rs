@@ -0,0 +1,30 @@
+use amethyst::{
+    core::math::Vector3,
+    ecs::{Component, DenseVecStorage},
+};
+
+/// Translations of a moving entity before and after the last fixed update's ticks,
+/// rendered frames blend between them so movement is smooth at any frame rate
+#[derive(Debug, Clone, Copy)]
+pub struct TickPosition {
+    pub previous: Vector3<f32>,
+    pub current: Vector3<f32>,
+}
+
+impl TickPosition {
+    pub fn new(position: Vector3<f32>) -> Self {
+        Self {
+            previous: position,
+            current: position,
+        }
+    }
+
+    /// Position a fraction alpha of the way from previous to current
+    pub fn blend(&self, alpha: f32) -> Vector3<f32> {
+        self.previous + (self.current - self.previous) * alpha
+    }
+}
+
+impl Component for TickPosition {
+    type Storage = DenseVecStorage<Self>;
+}
\ No newline at end of file
@@ -1,37 +1,92 @@
 use amethyst::ecs::{
     Component, 
     DenseVecStorage,
-    NullStorage,
     Entity,
 };
+use serde::{Deserialize, Serialize};
+use crate::config::tower::TowerArchetype;
+
+/// Which creep in range a tower shoots at
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub enum TargetingMode {
+    /// Furthest along its path to the exit
+    First,
+    /// Least far along its path to the exit
+    Last,
+    Closest,
+    /// Most health
+    Strongest,
+    /// Least health
+    Weakest,
+}
+
+impl TargetingMode {
+    /// The mode after this one, for cycling through them
+    pub fn next(self) -> Self {
+        match self {
+            TargetingMode::First => TargetingMode::Last,
+            TargetingMode::Last => TargetingMode::Closest,
+            TargetingMode::Closest => TargetingMode::Strongest,
+            TargetingMode::Strongest => TargetingMode::Weakest,
+            TargetingMode::Weakest => TargetingMode::First,
+        }
+    }
+}
+
+impl Default for TargetingMode {
+    fn default() -> Self {
+        TargetingMode::Closest
+    }
+}
 
 #[derive(Default)]
 pub struct Tower {
+    /// Name of the TowerArchetype in the tower config
+    pub archetype: String,
+    /// The map cell the tower occupies
+    pub cell: (usize, usize),
+    /// Gold spent on the tower, including upgrades
+    pub value: u32,
+    /// Number of upgrades applied
+    pub level: usize,
     pub range: f32,
     pub target: Option<Entity>,
+    pub targeting: TargetingMode,
     pub elapsed: f32,
     pub frequency: f32,
 }
 
 impl Tower {
-    pub fn new(range: f32, frequency: f32,) -> Self {
+    pub fn new(archetype: &TowerArchetype, cell: (usize, usize)) -> Self {
         Self {
-            range,
+            archetype: archetype.name.clone(),
+            cell,
+            value: archetype.cost,
+            level: 0,
+            range: archetype.range,
             target: None,
+            targeting: TargetingMode::default(),
             elapsed: 0.,
-            frequency,
+            frequency: archetype.frequency,
         }
     }
+
+    /// Applies the archetype's next upgrade. Returns false if the tower is already at max level
+    pub fn upgrade(&mut self, archetype: &TowerArchetype) -> bool {
+        let upgrade = match archetype.next_upgrade(self.level) {
+            Some(upgrade) => upgrade,
+            None => return false,
+        };
+        self.level += 1;
+        self.value += upgrade.cost;
+
+        let stats = archetype.stats(self.level);
+        self.range = stats.range;
+        self.frequency = stats.frequency;
+        true
+    }
 }
 
 impl Component for Tower {
     type Storage = DenseVecStorage<Self>;
-}
-
-
-#[derive(Default)]
-pub struct BulletTower;
-
-impl Component for BulletTower {
-    type Storage = NullStorage<Self>;
 }
\ No newline at end of file
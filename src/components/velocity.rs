@@ -7,7 +7,7 @@ use amethyst::{
     },
 };
 use crate::util::math::radians;
-use rand::random;
+use rand::Rng;
 
 union Transmute<T: Copy, U: Copy> {
     from: T,
@@ -41,11 +41,11 @@ impl Velocity {
         }
     }
 
-    pub fn rand(min: f32, max: f32) -> Self {
+    pub fn rand<R: Rng>(rng: &mut R, min: f32, max: f32) -> Self {
         let range = max - min;
-        let mag = random::<f32>() * range + min;
+        let mag = rng.gen::<f32>() * range + min;
 
-        let velocity = Vector2::new_random()
+        let velocity = Vector2::new(rng.gen::<f32>(), rng.gen::<f32>())
             .add_scalar(-0.5)
             .normalize()
             * mag;
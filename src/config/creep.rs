@@ -0,0 +1,75 @@
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CreepArchetype {
+    pub name: String,
+    /// Name of the character sprite sheet and animations to use
+    #[serde(default = "default_sprite")]
+    pub sprite: String,
+    pub health: f32,
+    /// Taken off the damage of every hit
+    #[serde(default)]
+    pub armour: f32,
+    /// Map cells per second
+    pub speed: f32,
+    /// Spawned speeds are spread up to this fraction of speed either side of it
+    #[serde(default)]
+    pub speed_variance: f32,
+    /// Gold given to the player for killing it
+    pub bounty: u32,
+    /// Scales the sprite and how close projectiles have to get to hit it
+    #[serde(default = "default_size")]
+    pub size: f32,
+}
+
+fn default_sprite() -> String {
+    "character_1".to_string()
+}
+
+fn default_size() -> f32 {
+    1.
+}
+
+impl CreepArchetype {
+    pub fn min_speed(&self) -> f32 {
+        self.speed * (1. - self.speed_variance)
+    }
+
+    pub fn max_speed(&self) -> f32 {
+        self.speed * (1. + self.speed_variance)
+    }
+}
+
+impl Default for CreepArchetype {
+  fn default() -> Self {
+    Self {
+        name: "Default".to_string(),
+        sprite: default_sprite(),
+        health: 10.,
+        armour: 0.,
+        speed: 25.,
+        speed_variance: 0.95,
+        bounty: 1,
+        size: default_size(),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Creep {
+    pub archetypes: Vec<CreepArchetype>,
+}
+
+impl Creep {
+    pub fn archetype(&self, name: &str) -> Option<&CreepArchetype> {
+        self.archetypes.iter().find(|a| a.name == name)
+    }
+}
+
+impl Default for Creep {
+  fn default() -> Self {
+    Self {
+        archetypes: vec!(Default::default()),
+    }
+  }
+}
\ No newline at end of file
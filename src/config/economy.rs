@@ -0,0 +1,17 @@
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Economy {
+    pub starting_gold: u32,
+    /// Fraction of the gold spent on a tower that is given back when it is sold
+    pub sell_refund: f32,
+}
+
+impl Default for Economy {
+  fn default() -> Self {
+    Self {
+        starting_gold: 100,
+        sell_refund: 0.5,
+    }
+  }
+}
\ No newline at end of file
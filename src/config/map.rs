@@ -1,6 +1,21 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize, Serialize)]
+///How navigators find their way to the exit
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub enum Navigation {
+  //Each navigator gets its own A* path
+  AStar,
+  //All navigators follow a shared flow field
+  FlowField,
+}
+
+impl Default for Navigation {
+  fn default() -> Self {
+    Navigation::AStar
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct Room {
   pub name: String,
   pub frequency: f32,
@@ -14,13 +29,14 @@ impl Default for Room {
       name: "Default".to_string(),
       frequency: 1.0,
       min_size: (8, 8),
-      max_size: (8, 8),
+      //Sizes are picked up to but not including max_size
+      max_size: (9, 9),
     }
   }
 }
 
 ///This is stuff that is used to create entities from the map
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct MapEntity {
   //What does "1" in map translate to in the real world
   pub unit_size: f32,
@@ -37,7 +53,7 @@ impl Default for MapEntity {
   }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct Map {
   pub width: u32,
   pub height: u32,
@@ -48,6 +64,11 @@ pub struct Map {
   pub entity: MapEntity,
   //Stuff inside this radius will have physics colliders
   pub physics_load_radius: f32,
+  //Fixed seed for generation, a random one is picked if this is None
+  pub seed: Option<u64>,
+  //How many entrances creeps can spawn at
+  pub entrance_count: u32,
+  pub navigation: Navigation,
 }
 
 impl Default for Map {
@@ -61,6 +82,9 @@ impl Default for Map {
       room_sizes: vec!(Default::default()),
       entity: Default::default(),
       physics_load_radius: 1000.0,
+      seed: None,
+      entrance_count: 1,
+      navigation: Default::default(),
     }
   }
 }
\ No newline at end of file
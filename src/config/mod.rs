@@ -15,12 +15,34 @@ pub use self::debug_draw::DebugDraw;
 pub mod tower;
 pub use self::tower::Tower;
 
-#[derive(Debug, Deserialize, Serialize, Default)]
+pub mod creep;
+pub use self::creep::Creep;
+
+pub mod waves;
+pub use self::waves::Waves;
+
+pub mod player;
+pub use self::player::Player;
+
+pub mod economy;
+pub use self::economy::Economy;
+
+pub mod simulation;
+pub use self::simulation::Simulation;
+
+pub mod validate;
+pub use self::validate::Problem;
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub struct Game {
     pub map: Map,
     pub spawner: Spawner,
     pub debug_draw: DebugDraw,
     pub tower: Tower,
+    pub creep: Creep,
+    pub player: Player,
+    pub economy: Economy,
+    pub simulation: Simulation,
 }
 
 impl Game {
@@ -33,5 +55,9 @@ impl Game {
             .with_resource(self.spawner)
             .with_resource(self.debug_draw)
             .with_resource(self.tower)
+            .with_resource(self.creep)
+            .with_resource(self.player)
+            .with_resource(self.economy)
+            .with_resource(self.simulation)
     }
 }
\ No newline at end of file
@@ -0,0 +1,15 @@
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Player {
+    /// Lives at the start of the game, one is lost for each creep reaching the exit
+    pub lives: u32,
+}
+
+impl Default for Player {
+  fn default() -> Self {
+    Self {
+        lives: 20,
+    }
+  }
+}
\ No newline at end of file
@@ -0,0 +1,15 @@
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Simulation {
+    /// Gameplay ticks per second, independent of the frame rate
+    pub tick_rate: u32,
+}
+
+impl Default for Simulation {
+  fn default() -> Self {
+    Self {
+        tick_rate: 60,
+    }
+  }
+}
\ No newline at end of file
@@ -1,11 +1,9 @@
 use serde::{Serialize, Deserialize};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Spawner {
+    /// Default seconds between spawns for wave entries without an interval
     pub spawn_delay: f32,
-    pub min_speed: f32,
-    pub max_speed: f32,
-    pub max_spawns: Option<usize>,
     pub max_age: Option<f32>,
     pub max_towers: Option<usize>,
 }
@@ -14,9 +12,6 @@ impl Default for Spawner {
   fn default() -> Self {
     Self {
         spawn_delay: 0.5,
-        min_speed: 1.,
-        max_speed: 50.,
-        max_spawns: Some(10),
         max_age: None,
         max_towers: Some(10),
     }
@@ -1,21 +1,215 @@
 use serde::{Serialize, Deserialize};
 
-#[derive(Debug, Deserialize, Serialize)]
-pub struct Tower {
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub enum ProjectileKind {
+    /// Damages the creep it hits
+    Bullet,
+    /// Damages every creep within radius (in map cells) of the creep it hits
+    Splash { radius: f32 },
+}
+
+impl Default for ProjectileKind {
+    fn default() -> Self {
+        ProjectileKind::Bullet
+    }
+}
+
+/// One step up a tower's upgrade tree. Any stat left out keeps its previous value
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct TowerUpgrade {
+    pub cost: u32,
+    pub sprite: Option<String>,
+    pub damage: Option<f32>,
+    pub range: Option<f32>,
+    pub frequency: Option<f32>,
+    pub projectile: Option<ProjectileKind>,
+    pub projectile_speed: Option<f32>,
+    pub aim_jitter: Option<f32>,
+}
+
+/// The combat stats of a tower at a particular level
+#[derive(Debug, Clone, Copy)]
+pub struct TowerStats {
+    pub damage: f32,
+    pub range: f32,
+    pub frequency: f32,
+    pub projectile: ProjectileKind,
+    pub projectile_speed: f32,
+    pub aim_jitter: f32,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TowerArchetype {
+    pub name: String,
+    /// Name of the animated sprite in the Sprites resource
+    pub sprite: String,
+    pub cost: u32,
+    pub damage: f32,
     pub range: f32,
     pub frequency: f32,
+    pub projectile: ProjectileKind,
     pub projectile_speed: f32,
     /// Jitter in radians
     pub aim_jitter: f32,
+    /// Applied in order, level 0 is the tower as built
+    #[serde(default)]
+    pub upgrades: Vec<TowerUpgrade>,
 }
 
-impl Default for Tower {
+impl TowerArchetype {
+    /// Highest level a tower of this archetype can reach
+    pub fn max_level(&self) -> usize {
+        self.upgrades.len()
+    }
+
+    /// The upgrade that takes a tower from level to level + 1, if there is one
+    pub fn next_upgrade(&self, level: usize) -> Option<&TowerUpgrade> {
+        self.upgrades.get(level)
+    }
+
+    /// Stats with the first level upgrades applied
+    pub fn stats(&self, level: usize) -> TowerStats {
+        let base = TowerStats {
+            damage: self.damage,
+            range: self.range,
+            frequency: self.frequency,
+            projectile: self.projectile,
+            projectile_speed: self.projectile_speed,
+            aim_jitter: self.aim_jitter,
+        };
+
+        self.upgrades.iter().take(level).fold(base, |stats, upgrade| TowerStats {
+            damage: upgrade.damage.unwrap_or(stats.damage),
+            range: upgrade.range.unwrap_or(stats.range),
+            frequency: upgrade.frequency.unwrap_or(stats.frequency),
+            projectile: upgrade.projectile.unwrap_or(stats.projectile),
+            projectile_speed: upgrade.projectile_speed.unwrap_or(stats.projectile_speed),
+            aim_jitter: upgrade.aim_jitter.unwrap_or(stats.aim_jitter),
+        })
+    }
+
+    /// Name of the sprite the tower uses at level
+    pub fn sprite(&self, level: usize) -> &str {
+        self.upgrades
+            .iter()
+            .take(level)
+            .filter_map(|u| u.sprite.as_ref())
+            .last()
+            .unwrap_or(&self.sprite)
+    }
+}
+
+impl Default for TowerArchetype {
   fn default() -> Self {
     Self {
+        name: "Default".to_string(),
+        sprite: "character_1".to_string(),
+        cost: 10,
+        damage: 1.,
         range: 10.,
         frequency: 0.5,
+        projectile: ProjectileKind::Bullet,
         projectile_speed: 200.,
         aim_jitter: 0.,
+        upgrades: Vec::new(),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Tower {
+    pub archetypes: Vec<TowerArchetype>,
+}
+
+impl Tower {
+    pub fn archetype(&self, name: &str) -> Option<&TowerArchetype> {
+        self.archetypes.iter().find(|a| a.name == name)
+    }
+}
+
+impl Default for Tower {
+  fn default() -> Self {
+    Self {
+        archetypes: vec!(Default::default()),
     }
   }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn archetype() -> TowerArchetype {
+        TowerArchetype {
+            damage: 1.,
+            range: 10.,
+            frequency: 0.5,
+            upgrades: vec!(
+                TowerUpgrade {
+                    cost: 10,
+                    damage: Some(2.),
+                    ..Default::default()
+                },
+                TowerUpgrade {
+                    cost: 20,
+                    sprite: Some("big_gun".to_string()),
+                    range: Some(12.),
+                    projectile: Some(ProjectileKind::Splash { radius: 1.5 }),
+                    ..Default::default()
+                },
+                TowerUpgrade {
+                    cost: 40,
+                    damage: Some(5.),
+                    frequency: Some(0.25),
+                    ..Default::default()
+                },
+            ),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn level_zero_uses_the_base_stats() {
+        let stats = archetype().stats(0);
+        assert_eq!(stats.damage, 1.);
+        assert_eq!(stats.range, 10.);
+        assert_eq!(stats.frequency, 0.5);
+        assert_eq!(stats.projectile, ProjectileKind::Bullet);
+    }
+
+    #[test]
+    fn upgrades_only_change_the_stats_they_set() {
+        let stats = archetype().stats(2);
+        assert_eq!(stats.damage, 2.);
+        assert_eq!(stats.range, 12.);
+        assert_eq!(stats.frequency, 0.5);
+        assert_eq!(stats.projectile, ProjectileKind::Splash { radius: 1.5 });
+    }
+
+    #[test]
+    fn later_upgrades_override_earlier_ones() {
+        let stats = archetype().stats(3);
+        assert_eq!(stats.damage, 5.);
+        assert_eq!(stats.range, 12.);
+        assert_eq!(stats.frequency, 0.25);
+        assert_eq!(stats.projectile, ProjectileKind::Splash { radius: 1.5 });
+    }
+
+    #[test]
+    fn levels_past_the_last_upgrade_stay_at_the_top() {
+        let archetype = archetype();
+        assert_eq!(archetype.max_level(), 3);
+        assert_eq!(archetype.stats(10).damage, archetype.stats(3).damage);
+        assert!(archetype.next_upgrade(3).is_none());
+    }
+
+    #[test]
+    fn sprite_is_the_last_one_set_by_an_upgrade() {
+        let archetype = archetype();
+        assert_eq!(archetype.sprite(0), "character_1");
+        assert_eq!(archetype.sprite(1), "character_1");
+        assert_eq!(archetype.sprite(2), "big_gun");
+        assert_eq!(archetype.sprite(3), "big_gun");
+    }
 }
\ No newline at end of file
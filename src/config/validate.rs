@@ -0,0 +1,388 @@
+use amethyst::config::Config;
+use std::{
+    collections::HashMap,
+    fmt,
+    path::Path,
+    process,
+};
+use super::{
+    Creep,
+    Game,
+    Map,
+    Spawner,
+    Tower,
+    Waves,
+    tower::{
+        ProjectileKind,
+        TowerUpgrade,
+    },
+    waves::SpawnPoint,
+};
+
+/// A config value the game can't work with and the field path to it
+#[derive(Debug, Clone)]
+pub struct Problem {
+    /// From the root of the file, like map.room_sizes[0].min_size.0
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// Collects problems so every one of them can be reported at once
+#[derive(Default)]
+struct Problems {
+    problems: Vec<Problem>,
+}
+
+impl Problems {
+    fn add(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.problems.push(Problem {
+            path: path.into(),
+            message: message.into(),
+        });
+    }
+
+    // The comparisons are written so NaN fails them too
+
+    fn at_least(&mut self, path: impl Into<String>, value: f32, min: f32) {
+        if !(value >= min) {
+            self.add(path, format!("is {} but must be at least {}", value, min));
+        }
+    }
+
+    fn above(&mut self, path: impl Into<String>, value: f32, min: f32) {
+        if !(value > min) {
+            self.add(path, format!("is {} but must be above {}", value, min));
+        }
+    }
+
+    fn between(&mut self, path: impl Into<String>, value: f32, min: f32, max: f32) {
+        if !(value >= min && value <= max) {
+            self.add(path, format!("is {} but must be between {} and {}", value, min, max));
+        }
+    }
+
+    /// Archetypes are looked up by name so only the first of a name would ever be used
+    fn unique_names<'a>(&mut self, path: &str, names: impl Iterator<Item = &'a str>) {
+        let mut seen = HashMap::new();
+        for (i, name) in names.enumerate() {
+            match seen.get(name) {
+                Some(first) => self.add(
+                    format!("{}[{}].name", path, i),
+                    format!("\"{}\" is already the name of {}[{}]", name, path, first),
+                ),
+                None => { seen.insert(name, i); },
+            }
+        }
+    }
+}
+
+impl Game {
+    /// Everything wrong with the config, it's fine to use if this is empty
+    pub fn validate(&self) -> Vec<Problem> {
+        let mut problems = Problems::default();
+        validate_map(&mut problems, &self.map);
+        validate_spawner(&mut problems, &self.spawner);
+        validate_tower(&mut problems, &self.tower);
+        validate_creep(&mut problems, &self.creep);
+
+        if self.player.lives == 0 {
+            problems.add("player.lives", "must be at least 1");
+        }
+        problems.between("economy.sell_refund", self.economy.sell_refund, 0., 1.);
+        if self.simulation.tick_rate == 0 {
+            problems.add("simulation.tick_rate", "must be at least 1");
+        }
+
+        problems.problems
+    }
+}
+
+impl Waves {
+    /// Everything wrong with the waves, including creeps and entrances the game config doesn't have
+    pub fn validate(&self, game: &Game) -> Vec<Problem> {
+        let mut problems = Problems::default();
+
+        for (i, wave) in self.waves.iter().enumerate() {
+            let path = format!("waves[{}]", i);
+            problems.at_least(format!("{}.delay", path), wave.delay, 0.);
+
+            for (j, entry) in wave.entries.iter().enumerate() {
+                let path = format!("{}.entries[{}]", path, j);
+                if game.creep.archetype(&entry.creep).is_none() {
+                    problems.add(format!("{}.creep", path), format!("there is no creep archetype called \"{}\"", entry.creep));
+                }
+                if let Some(interval) = entry.interval {
+                    problems.at_least(format!("{}.interval", path), interval, 0.);
+                }
+                if let SpawnPoint::Entrance(entrance) = entry.spawn_point {
+                    if entrance >= game.map.entrance_count as usize {
+                        problems.add(
+                            format!("{}.spawn_point", path),
+                            format!("entrance {} doesn't exist, map.entrance_count is {}", entrance, game.map.entrance_count),
+                        );
+                    }
+                }
+            }
+        }
+
+        problems.problems
+    }
+}
+
+fn validate_map(problems: &mut Problems, map: &Map) {
+    if map.width == 0 {
+        problems.add("map.width", "must be at least 1");
+    }
+    if map.height == 0 {
+        problems.add("map.height", "must be at least 1");
+    }
+    if map.room_place_max_iterations == 0 {
+        problems.add("map.room_place_max_iterations", "must be at least 1 or no rooms are placed");
+    }
+    problems.between("map.corridor_turn_chance", map.corridor_turn_chance, 0., 1.);
+    problems.at_least("map.superfluous_doors_per_room", map.superfluous_doors_per_room, 0.);
+    problems.above("map.entity.unit_size", map.entity.unit_size, 0.);
+    problems.at_least("map.entity.wall_height", map.entity.wall_height, 0.);
+    if map.entrance_count == 0 {
+        problems.add("map.entrance_count", "must be at least 1");
+    }
+
+    if map.room_sizes.is_empty() {
+        problems.add("map.room_sizes", "needs at least one room");
+    } else if !map.room_sizes.iter().any(|room| room.frequency > 0.) {
+        // Rooms are only tried once their chance adds up, the placement loop never ends otherwise
+        problems.add("map.room_sizes", "at least one room needs a frequency above 0");
+    }
+
+    for (i, room) in map.room_sizes.iter().enumerate() {
+        let path = format!("map.room_sizes[{}]", i);
+        problems.at_least(format!("{}.frequency", path), room.frequency, 0.);
+
+        let axes = [
+            (0, room.min_size.0, room.max_size.0, "width", map.width),
+            (1, room.min_size.1, room.max_size.1, "height", map.height),
+        ];
+        for &(axis, min, max, dimension, map_size) in axes.iter() {
+            let min_path = format!("{}.min_size.{}", path, axis);
+            if min == 0 {
+                problems.add(min_path, "must be at least 1");
+            } else if min >= max {
+                // Sizes are picked from min_size up to but not including max_size
+                problems.add(min_path, format!("is {} but must be less than max_size.{} ({})", min, axis, max));
+            } else if min.saturating_add(2) >= map_size {
+                // Rooms need a wall on each side inside the map
+                problems.add(min_path, format!("rooms of {} never fit in a map {} of {}", min, dimension, map_size));
+            }
+        }
+    }
+}
+
+fn validate_spawner(problems: &mut Problems, spawner: &Spawner) {
+    problems.at_least("spawner.spawn_delay", spawner.spawn_delay, 0.);
+    if let Some(max_age) = spawner.max_age {
+        problems.above("spawner.max_age", max_age, 0.);
+    }
+}
+
+fn validate_tower(problems: &mut Problems, tower: &Tower) {
+    problems.unique_names("tower.archetypes", tower.archetypes.iter().map(|a| a.name.as_str()));
+
+    for (i, archetype) in tower.archetypes.iter().enumerate() {
+        let path = format!("tower.archetypes[{}]", i);
+
+        // The base stats are checked the same way as an upgrade that sets all of them
+        let base = TowerUpgrade {
+            cost: archetype.cost,
+            sprite: Some(archetype.sprite.clone()),
+            damage: Some(archetype.damage),
+            range: Some(archetype.range),
+            frequency: Some(archetype.frequency),
+            projectile: Some(archetype.projectile),
+            projectile_speed: Some(archetype.projectile_speed),
+            aim_jitter: Some(archetype.aim_jitter),
+        };
+        validate_tower_upgrade(problems, &path, &base);
+
+        for (j, upgrade) in archetype.upgrades.iter().enumerate() {
+            validate_tower_upgrade(problems, &format!("{}.upgrades[{}]", path, j), upgrade);
+        }
+    }
+}
+
+fn validate_tower_upgrade(problems: &mut Problems, path: &str, upgrade: &TowerUpgrade) {
+    if let Some(damage) = upgrade.damage {
+        problems.at_least(format!("{}.damage", path), damage, 0.);
+    }
+    if let Some(range) = upgrade.range {
+        problems.above(format!("{}.range", path), range, 0.);
+    }
+    if let Some(frequency) = upgrade.frequency {
+        problems.above(format!("{}.frequency", path), frequency, 0.);
+    }
+    if let Some(ProjectileKind::Splash { radius }) = upgrade.projectile {
+        problems.above(format!("{}.projectile.radius", path), radius, 0.);
+    }
+    if let Some(projectile_speed) = upgrade.projectile_speed {
+        problems.above(format!("{}.projectile_speed", path), projectile_speed, 0.);
+    }
+    if let Some(aim_jitter) = upgrade.aim_jitter {
+        problems.at_least(format!("{}.aim_jitter", path), aim_jitter, 0.);
+    }
+}
+
+fn validate_creep(problems: &mut Problems, creep: &Creep) {
+    if creep.archetypes.is_empty() {
+        problems.add("creep.archetypes", "needs at least one archetype");
+    }
+    problems.unique_names("creep.archetypes", creep.archetypes.iter().map(|a| a.name.as_str()));
+
+    for (i, archetype) in creep.archetypes.iter().enumerate() {
+        let path = format!("creep.archetypes[{}]", i);
+        problems.above(format!("{}.health", path), archetype.health, 0.);
+        problems.at_least(format!("{}.armour", path), archetype.armour, 0.);
+        problems.above(format!("{}.speed", path), archetype.speed, 0.);
+        problems.between(format!("{}.speed_variance", path), archetype.speed_variance, 0., 1.);
+        problems.above(format!("{}.size", path), archetype.size, 0.);
+    }
+}
+
+/// Every problem in a game and waves config that are used together
+pub fn validate(game: &Game, waves: &Waves) -> Vec<Problem> {
+    let mut problems = game.validate();
+    problems.extend(waves.validate(game));
+    problems
+}
+
+/// One problem per line under a count of them
+pub fn report(problems: &[Problem]) -> String {
+    let mut report = format!("Found {} problem{} in the config:", problems.len(), if problems.len() == 1 { "" } else { "s" });
+    for problem in problems {
+        report.push_str(&format!("\n  {}", problem));
+    }
+    report
+}
+
+/// Validates configs loaded some other way, like from a replay, printing every problem and exiting if there are any
+pub fn validate_or_exit(game: &Game, waves: &Waves) {
+    let problems = validate(game, waves);
+    if !problems.is_empty() {
+        eprintln!("{}", report(&problems));
+        process::exit(1);
+    }
+}
+
+/// Loads and validates both configs, printing what's wrong and exiting if they can't be used
+pub fn load_or_exit(game_path: &Path, waves_path: &Path) -> (Game, Waves) {
+    let game = Game::load_no_fallback(game_path).unwrap_or_else(|e| {
+        eprintln!("Failed to load {:?}: {}", game_path, e);
+        process::exit(1);
+    });
+    let waves = Waves::load_no_fallback(waves_path).unwrap_or_else(|e| {
+        eprintln!("Failed to load {:?}: {}", waves_path, e);
+        process::exit(1);
+    });
+
+    validate_or_exit(&game, &waves);
+    (game, waves)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(problems: &[Problem]) -> Vec<&str> {
+        problems.iter().map(|p| p.path.as_str()).collect()
+    }
+
+    #[test]
+    fn default_config_is_valid() {
+        let game = Game::default();
+        assert!(validate(&game, &Waves::default()).is_empty());
+    }
+
+    #[test]
+    fn room_min_size_must_be_less_than_max_size() {
+        let mut game = Game::default();
+        game.map.room_sizes[0].min_size = (12, 8);
+        game.map.room_sizes[0].max_size = (10, 10);
+        assert_eq!(paths(&game.validate()), vec!("map.room_sizes[0].min_size.0"));
+
+        game.map.room_sizes[0].min_size = (8, 10);
+        assert_eq!(paths(&game.validate()), vec!("map.room_sizes[0].min_size.1"));
+    }
+
+    #[test]
+    fn spawn_delay_cant_be_negative() {
+        let mut game = Game::default();
+        game.spawner.spawn_delay = -1.;
+        assert_eq!(paths(&game.validate()), vec!("spawner.spawn_delay"));
+    }
+
+    #[test]
+    fn rooms_have_to_fit_in_the_map() {
+        let mut game = Game::default();
+        game.map.width = 20;
+        game.map.height = 6;
+        assert_eq!(paths(&game.validate()), vec!("map.room_sizes[0].min_size.1"));
+
+        game.map.width = 6;
+        assert_eq!(paths(&game.validate()), vec!("map.room_sizes[0].min_size.0", "map.room_sizes[0].min_size.1"));
+    }
+
+    #[test]
+    fn rooms_need_a_frequency() {
+        let mut game = Game::default();
+        game.map.room_sizes[0].frequency = 0.;
+        assert_eq!(paths(&game.validate()), vec!("map.room_sizes"));
+    }
+
+    #[test]
+    fn nan_is_never_valid() {
+        let mut game = Game::default();
+        game.economy.sell_refund = std::f32::NAN;
+        assert_eq!(paths(&game.validate()), vec!("economy.sell_refund"));
+    }
+
+    #[test]
+    fn archetype_names_must_be_unique() {
+        let mut game = Game::default();
+        game.creep.archetypes.push(game.creep.archetypes[0].clone());
+        assert_eq!(paths(&game.validate()), vec!("creep.archetypes[1].name"));
+    }
+
+    #[test]
+    fn tower_upgrades_are_checked() {
+        let mut game = Game::default();
+        game.tower.archetypes[0].upgrades.push(TowerUpgrade {
+            range: Some(0.),
+            ..Default::default()
+        });
+        assert_eq!(paths(&game.validate()), vec!("tower.archetypes[0].upgrades[0].range"));
+    }
+
+    #[test]
+    fn waves_must_name_known_creeps_and_entrances() {
+        let game = Game::default();
+        let mut waves = Waves::default();
+        waves.waves[0].entries[0].creep = "Nobody".to_string();
+        waves.waves[0].entries[0].spawn_point = SpawnPoint::Entrance(1);
+        assert_eq!(paths(&waves.validate(&game)), vec!("waves[0].entries[0].creep", "waves[0].entries[0].spawn_point"));
+    }
+
+    #[test]
+    fn every_problem_is_reported() {
+        let mut game = Game::default();
+        game.spawner.spawn_delay = -1.;
+        game.player.lives = 0;
+        game.simulation.tick_rate = 0;
+        let problems = game.validate();
+        assert_eq!(paths(&problems), vec!("spawner.spawn_delay", "player.lives", "simulation.tick_rate"));
+        assert!(report(&problems).starts_with("Found 3 problems in the config:"));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,54 @@
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub enum SpawnPoint {
+    /// An entrance picked at random for each creep
+    Random,
+    /// The entrance with this index in Map::entrances
+    Entrance(usize),
+}
+
+impl Default for SpawnPoint {
+    fn default() -> Self {
+        SpawnPoint::Random
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WaveEntry {
+    /// Name of the CreepArchetype in the creep config
+    pub creep: String,
+    pub count: usize,
+    /// Seconds between spawns, spawner.spawn_delay is used if this is None
+    pub interval: Option<f32>,
+    pub spawn_point: SpawnPoint,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Wave {
+    /// Seconds to wait after the previous wave has finished spawning
+    pub delay: f32,
+    /// Entries spawn alongside each other, the wave is done when all of them are
+    pub entries: Vec<WaveEntry>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Waves {
+    pub waves: Vec<Wave>,
+}
+
+impl Default for Waves {
+  fn default() -> Self {
+    Self {
+        waves: vec!(Wave {
+            delay: 0.,
+            entries: vec!(WaveEntry {
+                creep: "Default".to_string(),
+                count: 10,
+                interval: None,
+                spawn_point: SpawnPoint::Random,
+            }),
+        }),
+    }
+  }
+}
\ No newline at end of file
@@ -0,0 +1,326 @@
+use amethyst::{
+    core::frame_limiter::FrameRateLimitStrategy,
+    ecs::prelude::{Join, RunNow},
+    prelude::*,
+    renderer::{
+        debug_drawing::DebugLinesComponent,
+        Transparent,
+    },
+    utils::application_root_dir,
+};
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+use crate::{
+    components::{
+        Animation,
+        Map,
+    },
+    config::{
+        validate,
+        Map as MapConfig,
+        Spawner as SpawnerConfig,
+        Tower as TowerConfig,
+    },
+    resources::{
+        Command,
+        Commands,
+        Gold,
+        Lives,
+        SimClock,
+        SimRng,
+        Stats,
+        WaveStatus,
+    },
+    systems::Heading,
+    util::{
+        Simulation,
+        replay::{
+            self,
+            Replay,
+        },
+    },
+};
+
+/// Ticks simulated between checks for the end of the game
+const TICKS_PER_UPDATE: u32 = 600;
+
+/// Gives up on games that go on longer than this many seconds of game time
+const MAX_GAME_SECONDS: f32 = 60. * 60.;
+
+/// Settings for a headless run
+#[derive(Debug, Default)]
+pub struct Options {
+    /// Defaults to config/game_config.ron
+    pub config: Option<PathBuf>,
+    /// Defaults to config/waves.ron
+    pub waves: Option<PathBuf>,
+    /// Overrides the map seed in the game config
+    pub seed: Option<u64>,
+    /// Only play the first wave_count waves
+    pub wave_count: Option<usize>,
+    /// RON list of Commands to run before the first tick. Without one a tower
+    /// is placed in each room, cycling through the archetypes
+    pub towers: Option<PathBuf>,
+    /// Replay to play back. Its configs are used and the other options are ignored
+    pub replay: Option<PathBuf>,
+    /// Where to write a replay of the run
+    pub record: Option<PathBuf>,
+    /// Only validate the configs and exit
+    pub check_config: bool,
+    /// Picks and plays out animations between ticks like the windowed game does,
+    /// to check the outcome doesn't depend on whether the game is rendered
+    pub animate: bool,
+}
+
+/// Plays a game with no window or rendering as fast as possible and prints the Stats.
+/// Returns the Stats at the end, None if only the configs were checked
+pub fn run(options: Options) -> amethyst::Result<Option<Stats>> {
+    let app_root_path = application_root_dir()?;
+    let assets_path = app_root_path.join("assets");
+    let config_path = app_root_path.join("config");
+    let game_config_path = options.config.clone().unwrap_or_else(|| config_path.join("game_config.ron"));
+    let waves_config_path = options.waves.clone().unwrap_or_else(|| config_path.join("waves.ron"));
+
+    let (mut game_config, waves_config, commands, towers) = match &options.replay {
+        // The replay's commands place the towers
+        Some(path) => {
+            let (game_config, waves_config, commands) = Replay::read(path).expect("Failed to load replay").into_parts();
+            validate::validate_or_exit(&game_config, &waves_config);
+            (game_config, waves_config, commands, Some(Vec::new()))
+        },
+        None => {
+            let (mut game_config, mut waves_config) = validate::load_or_exit(&game_config_path, &waves_config_path);
+
+            if let Some(seed) = options.seed {
+                game_config.map.seed = Some(seed);
+            }
+            if let Some(wave_count) = options.wave_count {
+                waves_config.waves.truncate(wave_count);
+            }
+
+            let towers = options.towers.as_ref().map(|path| {
+                Vec::<Command>::load_no_fallback(path).expect("Failed to load tower layout")
+            });
+            (game_config, waves_config, Vec::new(), towers)
+        },
+    };
+    if options.check_config {
+        println!("Config is valid");
+        return Ok(None);
+    }
+
+    let final_stats = Arc::new(Mutex::new(None));
+    let builder = Application::build(assets_path, Headless::new(towers, options.animate, final_stats.clone()))?
+        .with_frame_limit(FrameRateLimitStrategy::Unlimited, 0)
+        // Added to spawned entities but never used without rendering
+        .register::<Transparent>()
+        .register::<DebugLinesComponent>();
+    let (builder, seed) = replay::register(builder, &game_config, &waves_config, commands, options.record.clone());
+    let builder = crate::register_game(builder, game_config, waves_config, seed);
+
+    let mut game = builder.build(GameDataBuilder::default())?;
+    game.run();
+
+    let stats = final_stats.lock().expect("Final stats lock poisoned").take();
+    Ok(stats)
+}
+
+/// Tower placements for when no layout is given, one in the middle of each room
+fn room_layout(map: &Map, tower_config: &TowerConfig, spawner_config: &SpawnerConfig, gold: u32) -> Vec<Command> {
+    let mut commands = Vec::new();
+    if tower_config.archetypes.is_empty() {
+        return commands;
+    }
+
+    let max_towers = spawner_config.max_towers.unwrap_or(usize::max_value());
+    let mut gold = gold;
+    for room in map.rooms() {
+        if commands.len() >= max_towers { break }
+
+        let archetype = &tower_config.archetypes[commands.len() % tower_config.archetypes.len()];
+        if archetype.cost > gold { break }
+
+        let cell = ((room.start().x + room.end().x) / 2, (room.start().y + room.end().y) / 2);
+        if map.can_place_tower(cell).is_err() { continue }
+
+        gold -= archetype.cost;
+        commands.push(Command::PlaceTower {
+            cell,
+            archetype: archetype.name.clone(),
+        });
+    }
+    commands
+}
+
+struct Headless {
+    /// Placements given on the command line, taken on start
+    towers: Option<Vec<Command>>,
+    simulation: Option<Simulation>,
+    animate: bool,
+    /// Handed back to run once the game is over
+    final_stats: Arc<Mutex<Option<Stats>>>,
+}
+
+impl Headless {
+    fn new(towers: Option<Vec<Command>>, animate: bool, final_stats: Arc<Mutex<Option<Stats>>>) -> Self {
+        Self {
+            towers,
+            simulation: None,
+            animate,
+            final_stats,
+        }
+    }
+
+    /// Stands in for the windowed game's frame systems. Heading picks the walk
+    /// animations and every animation finishes straight away, as on a fast frame
+    fn play_animations(world: &mut World) {
+        Heading.run_now(world);
+        let mut animations = world.write_storage::<Animation>();
+        for a in (&mut animations).join() {
+            a.current = a.next;
+            a.is_done = true;
+        }
+    }
+
+    fn is_finished(world: &World) -> bool {
+        world.read_resource::<WaveStatus>().cleared ||
+        world.read_resource::<Lives>().lives == 0 ||
+        world.read_resource::<SimClock>().elapsed() >= MAX_GAME_SECONDS
+    }
+
+    fn print_report(world: &World) {
+        let stats = world.read_resource::<Stats>();
+        let wave_status = world.read_resource::<WaveStatus>();
+        let clock = world.read_resource::<SimClock>();
+        let maps = world.read_storage::<Map>();
+        let seed = (&maps).join().next().map(|m| m.seed());
+
+        println!("seed:           {}", seed.map_or("none".to_string(), |s| s.to_string()));
+        println!("waves:          {} of {}{}", wave_status.wave, wave_status.wave_count, if wave_status.cleared { " cleared" } else { "" });
+        println!("game time:      {:.1}s ({} ticks)", clock.elapsed(), clock.tick);
+        println!("lives left:     {}", world.read_resource::<Lives>().lives);
+        println!("kills:          {}", stats.kills);
+        println!("leaks:          {}", stats.leaks);
+        println!("towers built:   {}", stats.towers_built);
+        println!("towers sold:    {}", stats.towers_sold);
+        println!("upgrades:       {}", stats.tower_upgrades);
+        println!("gold earned:    {}", stats.gold_earned);
+        println!("gold spent:     {}", stats.gold_spent);
+        println!("gold left:      {}", world.read_resource::<Gold>().gold);
+    }
+}
+
+impl SimpleState for Headless {
+    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        let world = data.world;
+        world.insert(Commands::default());
+        world.insert(Stats::default());
+
+        // MapGenerator needs sprites for the tiles so the map is made here instead
+        let map = {
+            let map_config = world.read_resource::<MapConfig>();
+            let mut map = Map::new(map_config.width, map_config.height);
+            map.generate_with_seed(&map_config, world.read_resource::<SimRng>().map_seed(&map_config));
+            map
+        };
+
+        let towers = self.towers.take().unwrap_or_else(|| room_layout(
+            &map,
+            &world.read_resource::<TowerConfig>(),
+            &world.read_resource::<SpawnerConfig>(),
+            world.read_resource::<Gold>().gold,
+        ));
+        for command in towers {
+            world.write_resource::<Commands>().push(command);
+        }
+
+        world.create_entity().with(map).build();
+        self.simulation = Some(Simulation::new(world));
+    }
+
+    fn update(&mut self, data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
+        let simulation = match self.simulation.as_mut() {
+            Some(simulation) => simulation,
+            None => return Trans::Quit,
+        };
+
+        for _ in 0..TICKS_PER_UPDATE {
+            simulation.tick(data.world);
+            if self.animate {
+                Self::play_animations(data.world);
+            }
+            if Self::is_finished(data.world) {
+                replay::write_recording(data.world);
+                Self::print_report(data.world);
+                *self.final_stats.lock().expect("Final stats lock poisoned") = Some(data.world.read_resource::<Stats>().clone());
+                return Trans::Quit;
+            }
+        }
+        Trans::None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, fs};
+
+    fn replay_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("headless_{}_{}.ron", name, std::process::id()))
+    }
+
+    #[test]
+    fn replay_plays_back_the_recorded_game() {
+        let path = replay_path("replay");
+
+        let recorded = run(Options {
+            seed: Some(42),
+            wave_count: Some(1),
+            record: Some(path.clone()),
+            ..Default::default()
+        }).expect("Recording failed").expect("No stats from the recording");
+
+        let played_back = run(Options {
+            replay: Some(path.clone()),
+            ..Default::default()
+        }).expect("Playback failed").expect("No stats from the playback");
+        fs::remove_file(&path).ok();
+
+        assert!(recorded.towers_built > 0, "the room layout should place towers: {:?}", recorded);
+        assert_eq!(recorded, played_back);
+    }
+
+    #[test]
+    fn animated_recording_plays_back_the_same_without_animations() {
+        let path = replay_path("animated");
+        let playback_path = replay_path("animated_playback");
+
+        let recorded = run(Options {
+            seed: Some(42),
+            wave_count: Some(1),
+            record: Some(path.clone()),
+            animate: true,
+            ..Default::default()
+        }).expect("Recording failed").expect("No stats from the recording");
+
+        // Recorded again so the tick it finished on can be compared too
+        let played_back = run(Options {
+            replay: Some(path.clone()),
+            record: Some(playback_path.clone()),
+            ..Default::default()
+        }).expect("Playback failed").expect("No stats from the playback");
+
+        let end_ticks = (
+            Replay::read(&path).expect("Failed to read recording").end_tick,
+            Replay::read(&playback_path).expect("Failed to read playback").end_tick,
+        );
+        fs::remove_file(&path).ok();
+        fs::remove_file(&playback_path).ok();
+
+        assert!(recorded.kills + recorded.leaks > 0, "the wave should have played out: {:?}", recorded);
+        assert_eq!(recorded, played_back);
+        assert_eq!(end_ticks.0, end_ticks.1);
+    }
+}
\ No newline at end of file
@@ -2,6 +2,8 @@
 
 use amethyst::{
     core::transform::TransformBundle,
+    ApplicationBuilder,
+    DataDispose,
     prelude::*,
     renderer::{
         plugins::{RenderFlat2D, RenderToWindow, RenderDebugLines},
@@ -23,6 +25,10 @@ use amethyst::{
     controls::MouseFocusUpdateSystemDesc,
 };
 use log::LevelFilter;
+use std::{
+    path::PathBuf,
+    time::Duration,
+};
 
 pub mod states;
 pub mod resources;
@@ -30,8 +36,9 @@ pub mod components;
 pub mod systems;
 pub mod config;
 pub mod util;
+pub mod headless;
 
-fn create_logger(level: LevelFilter) {
+pub fn create_logger(level: LevelFilter) {
   use std::io;
 
   let gfx_device_gl_level = if level > LevelFilter::Warn {
@@ -61,7 +68,18 @@ fn create_logger(level: LevelFilter) {
     .expect("Failed to create fern logger");
 }
 
-pub fn run() -> amethyst::Result<()> {
+/// Settings for the windowed game
+#[derive(Debug, Default)]
+pub struct Options {
+    /// Replay to play back instead of taking commands from the player
+    pub replay: Option<PathBuf>,
+    /// Where to write a replay of the game when it ends
+    pub record: Option<PathBuf>,
+    /// Only validate the configs and exit
+    pub check_config: bool,
+}
+
+pub fn run(options: Options) -> amethyst::Result<()> {
     create_logger(LevelFilter::Info);
 
     let app_root_path = application_root_dir()?;
@@ -70,10 +88,25 @@ pub fn run() -> amethyst::Result<()> {
     let display_config_path = config_path.join("display_config.ron");
     let binding_config_path = config_path.join("binding_config.ron");
     let game_config_path = config_path.join("game_config.ron");
+    let waves_config_path = config_path.join("waves.ron");
 
-    let game_config = config::Game::load_no_fallback(&game_config_path).expect("Failed to load game config");
+    let (game_config, waves_config, commands) = match &options.replay {
+        Some(path) => {
+            let (game_config, waves_config, commands) = util::replay::Replay::read(path).expect("Failed to load replay").into_parts();
+            config::validate::validate_or_exit(&game_config, &waves_config);
+            (game_config, waves_config, commands)
+        },
+        None => {
+            let (game_config, waves_config) = config::validate::load_or_exit(&game_config_path, &waves_config_path);
+            (game_config, waves_config, Vec::new())
+        },
+    };
+    if options.check_config {
+        println!("Config is valid");
+        return Ok(());
+    }
 
-    let game_data = GameDataBuilder::default()
+    let mut game_data = GameDataBuilder::default()
         .with(Processor::<resources::NamedSpriteSheet>::new(), "", &[])
         .with(Processor::<resources::NamedAnimationSet>::new(), "", &[])
         /*
@@ -111,12 +144,17 @@ pub fn run() -> amethyst::Result<()> {
             "mouse_focus_update_system",
             &[]);
 
-    let mut builder = Application::build(
+    // A replay has to keep the config it was recorded with
+    if options.replay.is_none() {
+        game_data = game_data.with(systems::ConfigReloader::new(game_config_path), "config_reloader_system", &[]);
+    }
+
+    let builder = Application::build(
         assets_path, 
         states::Loading::default(),
     )?;
-    builder = game_config.register(builder);
-    builder = components::register_components(builder);
+    let (builder, seed) = util::replay::register(builder, &game_config, &waves_config, commands, options.record);
+    let builder = register_game(builder, game_config, waves_config, seed);
     
     let mut game = builder
         .build(game_data)?;
@@ -125,3 +163,30 @@ pub fn run() -> amethyst::Result<()> {
 
     Ok(())
 }
+
+/// Adds the configs, the gameplay resources they set up and the game's components.
+/// The seed comes from replay::register
+pub fn register_game<S, T, E, X>(
+    builder: ApplicationBuilder<S, T, E, X>,
+    game_config: config::Game,
+    waves_config: config::Waves,
+    seed: u64,
+) -> ApplicationBuilder<S, T, E, X>
+where
+    T: DataDispose + 'static,
+{
+    // The same seed drives map generation and gameplay so a game can be played out again
+    let clock = resources::SimClock::new(game_config.simulation.tick_rate);
+    let lives = resources::Lives::new(game_config.player.lives);
+    let gold = resources::Gold::new(game_config.economy.starting_gold);
+
+    let mut builder = builder.with_fixed_step_length(Duration::from_secs_f32(clock.tick_seconds));
+    builder = game_config.register(builder);
+    builder = builder
+        .with_resource(waves_config)
+        .with_resource(lives)
+        .with_resource(gold)
+        .with_resource(clock)
+        .with_resource(resources::SimRng::new(seed));
+    components::register_components(builder)
+}
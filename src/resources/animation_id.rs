@@ -35,6 +35,20 @@ impl AnimationId {
             _ => EndControl::Loop(None),
         }
     }
+    /// The death animation matching the direction of a walk animation
+    pub fn death(&self) -> Option<AnimationId> {
+        match self {
+            &AnimationId::WalkUp => Some(AnimationId::DieUp),
+            &AnimationId::WalkDown => Some(AnimationId::DieDown),
+            &AnimationId::WalkLeft => Some(AnimationId::DieLeft),
+            &AnimationId::WalkRight => Some(AnimationId::DieRight),
+            &AnimationId::WalkUpLeft => Some(AnimationId::DieUpLeft),
+            &AnimationId::WalkDownLeft => Some(AnimationId::DieDownLeft),
+            &AnimationId::WalkUpRight => Some(AnimationId::DieUpRight),
+            &AnimationId::WalkDownRight => Some(AnimationId::DieDownRight),
+            _ => None,
+        }
+    }
     pub fn is_terminal(&self) -> bool {
         match self {
             &AnimationId::DieUp |
@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use crate::components::TargetingMode;
+
+/// Actions requested by the player
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub enum Command {
+    PlaceTower {
+        cell: (usize, usize),
+        /// Name of the TowerArchetype in the tower config
+        archetype: String,
+    },
+    SellTower {
+        cell: (usize, usize),
+    },
+    UpgradeTower {
+        cell: (usize, usize),
+    },
+    SetTargeting {
+        cell: (usize, usize),
+        mode: TargetingMode,
+    },
+    /// Skips the rest of the countdown to the next wave
+    StartWave,
+}
+
+/// A command and the SimClock tick it was carried out on
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TimedCommand {
+    pub tick: u64,
+    pub command: Command,
+}
+
+/// Commands waiting for the CommandProcessor system
+#[derive(Debug, Default)]
+pub struct Commands {
+    pub pending: Vec<Command>,
+}
+
+impl Commands {
+    pub fn push(&mut self, command: Command) {
+        self.pending.push(command);
+    }
+}
+
+/// Every command the CommandProcessor has carried out, for recording replays
+#[derive(Debug, Default)]
+pub struct CommandLog {
+    pub commands: Vec<TimedCommand>,
+}
+
+/// Commands from a replay waiting for their tick. Player commands are ignored until it runs out
+#[derive(Debug, Default)]
+pub struct Playback {
+    pub commands: VecDeque<TimedCommand>,
+}
+
+impl Playback {
+    pub fn new(commands: Vec<TimedCommand>) -> Self {
+        Self {
+            commands: commands.into(),
+        }
+    }
+
+    pub fn is_playing(&self) -> bool {
+        !self.commands.is_empty()
+    }
+}
\ No newline at end of file
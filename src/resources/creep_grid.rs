@@ -0,0 +1,123 @@
+use amethyst::{
+    core::math::Vector2,
+    ecs::Entity,
+};
+use std::collections::HashMap;
+
+/// Width and height of a grid bucket in map cells
+const BUCKET_SIZE: f32 = 4.;
+
+/// Uniform grid of creep positions in map space so nearby creeps can be found
+/// without checking every one
+#[derive(Default)]
+pub struct CreepGrid {
+    buckets: HashMap<(i32, i32), Vec<(Entity, Vector2<f32>)>>,
+}
+
+impl CreepGrid {
+    fn bucket(pos: Vector2<f32>) -> (i32, i32) {
+        ((pos.x / BUCKET_SIZE).floor() as i32, (pos.y / BUCKET_SIZE).floor() as i32)
+    }
+
+    /// Empties the buckets but keeps their allocations for the next rebuild
+    pub fn clear(&mut self) {
+        for bucket in self.buckets.values_mut() {
+            bucket.clear();
+        }
+    }
+
+    pub fn insert(&mut self, entity: Entity, pos: Vector2<f32>) {
+        self.buckets
+            .entry(Self::bucket(pos))
+            .or_insert_with(Vec::new)
+            .push((entity, pos));
+    }
+
+    /// Creeps within radius of center, along with their distance from it
+    pub fn query<'a>(&'a self, center: Vector2<f32>, radius: f32) -> impl Iterator<Item = (Entity, f32)> + 'a {
+        let (min_x, min_y) = Self::bucket(center - Vector2::new(radius, radius));
+        let (max_x, max_y) = Self::bucket(center + Vector2::new(radius, radius));
+
+        (min_x..=max_x)
+            .flat_map(move |x| (min_y..=max_y).map(move |y| (x, y)))
+            .filter_map(move |b| self.buckets.get(&b))
+            .flat_map(|bucket| bucket.iter())
+            .filter_map(move |(entity, pos)| {
+                let distance = pos.metric_distance(&center);
+                if distance <= radius {
+                    Some((*entity, distance))
+                } else {
+                    None
+                }
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cmp::Ordering;
+    use amethyst::ecs::{
+        Builder,
+        World,
+        WorldExt,
+    };
+
+    fn sorted(mut found: Vec<(Entity, f32)>) -> Vec<(Entity, f32)> {
+        found.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal).then(a.0.id().cmp(&b.0.id())));
+        found
+    }
+
+    #[test]
+    fn finds_creeps_within_the_radius() {
+        let mut world = World::new();
+        let near = world.create_entity().build();
+        let far = world.create_entity().build();
+
+        let mut grid = CreepGrid::default();
+        grid.insert(near, Vector2::new(1., 0.));
+        grid.insert(far, Vector2::new(10., 0.));
+
+        let found = sorted(grid.query(Vector2::new(0., 0.), 2.).collect());
+        assert_eq!(found, vec!((near, 1.)));
+    }
+
+    #[test]
+    fn finds_creeps_in_neighbouring_buckets() {
+        let mut world = World::new();
+        let left = world.create_entity().build();
+        let right = world.create_entity().build();
+
+        // Either side of the bucket boundary at x = 4
+        let mut grid = CreepGrid::default();
+        grid.insert(left, Vector2::new(3.5, 1.));
+        grid.insert(right, Vector2::new(4.5, 1.));
+
+        let found = sorted(grid.query(Vector2::new(4., 1.), 1.).collect());
+        assert_eq!(found, vec!((left, 0.5), (right, 0.5)));
+    }
+
+    #[test]
+    fn includes_creeps_on_the_edge_of_the_radius() {
+        let mut world = World::new();
+        let creep = world.create_entity().build();
+
+        let mut grid = CreepGrid::default();
+        grid.insert(creep, Vector2::new(-3., -4.));
+
+        let found: Vec<_> = grid.query(Vector2::new(0., 0.), 5.).collect();
+        assert_eq!(found, vec!((creep, 5.)));
+    }
+
+    #[test]
+    fn clear_empties_the_grid() {
+        let mut world = World::new();
+        let creep = world.create_entity().build();
+
+        let mut grid = CreepGrid::default();
+        grid.insert(creep, Vector2::new(0., 0.));
+        grid.clear();
+
+        assert_eq!(grid.query(Vector2::new(0., 0.), 10.).count(), 0);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,173 @@
+use amethyst::ecs::Entity;
+use ndarray::Array2;
+use pathfinding::prelude::dijkstra_all;
+use std::collections::HashMap;
+use crate::components::Map;
+
+/// Integration and direction fields that lead every reachable cell to a single goal
+pub struct FlowField {
+    goal: (usize, usize),
+    /// Path cost to the goal, None where the goal can't be reached
+    integration: Array2<Option<i32>>,
+    /// The next cell to move to on the way to the goal
+    directions: Array2<Option<(usize, usize)>>,
+}
+
+impl FlowField {
+    pub fn new(map: &Map, goal: (usize, usize)) -> Self {
+        let size = (map.width() as usize, map.height() as usize);
+        let mut integration = Array2::from_elem(size, None);
+        let mut directions = Array2::from_elem(size, None);
+
+        integration[goal] = Some(0);
+
+        // Successors are symmetric so searching out from the goal gives each
+        // cell's parent as its next step towards the goal
+        for (cell, (parent, cost)) in dijkstra_all(&goal, |&p| map.successors(p)) {
+            integration[cell] = Some(cost);
+            directions[cell] = Some(parent);
+        }
+
+        Self {
+            goal,
+            integration,
+            directions,
+        }
+    }
+
+    pub fn goal(&self) -> (usize, usize) {
+        self.goal
+    }
+
+    /// Cost of the path from cell to the goal
+    pub fn cost(&self, cell: (usize, usize)) -> Option<i32> {
+        self.integration.get(cell).cloned().unwrap_or(None)
+    }
+
+    /// Where to go next from cell, None at the goal or if it can't be reached
+    pub fn next(&self, cell: (usize, usize)) -> Option<(usize, usize)> {
+        self.directions.get(cell).cloned().unwrap_or(None)
+    }
+
+    /// Like next, but a cell that's off the field (a tower went up on it with a
+    /// navigator inside) leads to its cheapest neighbour that's still on the field
+    pub fn step_from(&self, cell: (usize, usize)) -> Option<(usize, usize)> {
+        if self.cost(cell).is_some() {
+            return self.next(cell);
+        }
+
+        let (x, y) = (cell.0 as isize, cell.1 as isize);
+        let neighbours = (-1..=1).flat_map(|dx| (-1..=1).map(move |dy| (x + dx, y + dy)));
+        neighbours
+            .filter(|&(nx, ny)| nx >= 0 && ny >= 0 && (nx, ny) != (x, y))
+            .map(|(nx, ny)| (nx as usize, ny as usize))
+            .filter_map(|n| self.cost(n).map(|cost| (cost, n)))
+            .min()
+            .map(|(_, n)| n)
+    }
+}
+
+/// Flow fields for each goal on the current map
+#[derive(Default)]
+pub struct FlowFields {
+    /// The map entity and Map::version the fields were built from
+    built_from: Option<(Entity, u32)>,
+    fields: HashMap<(usize, usize), FlowField>,
+}
+
+impl FlowFields {
+    pub fn is_current(&self, map_entity: Entity, map: &Map) -> bool {
+        self.built_from == Some((map_entity, map.version()))
+    }
+
+    pub fn rebuild(&mut self, map_entity: Entity, map: &Map, goals: &[(usize, usize)]) {
+        self.fields.clear();
+        for goal in goals {
+            self.fields.insert(*goal, FlowField::new(map, *goal));
+        }
+        self.built_from = Some((map_entity, map.version()));
+    }
+
+    pub fn get(&self, goal: (usize, usize)) -> Option<&FlowField> {
+        self.fields.get(&goal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn goal_costs_nothing_and_has_no_next_step() {
+        let map = Map::from_layout(&[
+            "#######",
+            "#E   X#",
+            "#######",
+        ]);
+        let field = FlowField::new(&map, (5, 1));
+        assert_eq!(field.goal(), (5, 1));
+        assert_eq!(field.cost((5, 1)), Some(0));
+        assert_eq!(field.next((5, 1)), None);
+    }
+
+    #[test]
+    fn corridor_cells_step_towards_the_goal() {
+        let map = Map::from_layout(&[
+            "#######",
+            "#E   X#",
+            "#######",
+        ]);
+        let field = FlowField::new(&map, (5, 1));
+        for x in 1..5 {
+            assert_eq!(field.next((x, 1)), Some((x + 1, 1)));
+            assert_eq!(field.cost((x, 1)), Some((5 - x as i32) * 100));
+        }
+    }
+
+    #[test]
+    fn open_cells_take_the_diagonal() {
+        let map = Map::from_layout(&[
+            "#####",
+            "#   #",
+            "#   #",
+            "#   #",
+            "#####",
+        ]);
+        let field = FlowField::new(&map, (3, 3));
+        assert_eq!(field.next((1, 1)), Some((2, 2)));
+        assert_eq!(field.cost((1, 1)), Some(284));
+    }
+
+    #[test]
+    fn walls_and_cut_off_cells_have_no_route() {
+        let map = Map::from_layout(&[
+            "#######",
+            "#  #  #",
+            "#######",
+        ]);
+        let field = FlowField::new(&map, (1, 1));
+        assert_eq!(field.cost((3, 1)), None);
+        assert_eq!(field.next((3, 1)), None);
+        assert_eq!(field.cost((5, 1)), None);
+        assert_eq!(field.next((5, 1)), None);
+        // Outside the map
+        assert_eq!(field.cost((10, 10)), None);
+    }
+    #[test]
+    fn stranded_cells_step_back_onto_the_field() {
+        let mut map = Map::from_layout(&[
+            "#######",
+            "#E   X#",
+            "#     #",
+            "#######",
+        ]);
+        // A tower went up on top of a navigator at (3, 1)
+        map.set_occupied((3, 1), true);
+        let field = FlowField::new(&map, (5, 1));
+        assert_eq!(field.next((3, 1)), None);
+        assert_eq!(field.step_from((3, 1)), Some((4, 1)));
+        // Cells on the field step the same way as next
+        assert_eq!(field.step_from((1, 1)), field.next((1, 1)));
+        assert_eq!(field.step_from((5, 1)), None);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub struct Gold {
+    pub gold: u32,
+}
+
+impl Gold {
+    pub fn new(gold: u32) -> Self {
+        Self {
+            gold,
+        }
+    }
+}
\ No newline at end of file
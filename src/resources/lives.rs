@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub struct Lives {
+    pub lives: u32,
+}
+
+impl Lives {
+    pub fn new(lives: u32) -> Self {
+        Self {
+            lives,
+        }
+    }
+}
\ No newline at end of file
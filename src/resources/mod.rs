@@ -1,6 +1,9 @@
 mod sprites;
 pub use sprites::*;
 
+mod sprite_manifest;
+pub use sprite_manifest::*;
+
 mod named_sprite_sheet;
 pub use named_sprite_sheet::*;
 
@@ -14,4 +17,43 @@ mod zoom;
 pub use zoom::*;
 
 mod camera_move;
-pub use camera_move::*;
\ No newline at end of file
+pub use camera_move::*;
+
+mod wave_status;
+pub use wave_status::*;
+
+mod flow_field;
+pub use flow_field::*;
+
+mod lives;
+pub use lives::*;
+
+mod commands;
+pub use commands::*;
+
+mod tower_placement;
+pub use tower_placement::*;
+
+mod gold;
+pub use gold::*;
+
+mod selection;
+pub use selection::*;
+
+mod creep_grid;
+pub use creep_grid::*;
+
+mod visibility;
+pub use visibility::*;
+
+mod sim_clock;
+pub use sim_clock::*;
+
+mod sim_rng;
+pub use sim_rng::*;
+
+mod stats;
+pub use stats::*;
+
+mod time_scale;
+pub use time_scale::*;
\ No newline at end of file
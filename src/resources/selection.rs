@@ -0,0 +1,5 @@
+/// The placed tower the player has clicked on
+#[derive(Debug, Default)]
+pub struct Selection {
+    pub cell: Option<(usize, usize)>,
+}
\ No newline at end of file
@@ -0,0 +1,32 @@
+/// Fixed step clock the gameplay systems advance with instead of the frame time
+#[derive(Debug, Clone, Copy)]
+pub struct SimClock {
+    /// Number of ticks simulated so far
+    pub tick: u64,
+    /// Seconds of game time covered by each tick
+    pub tick_seconds: f32,
+}
+
+impl SimClock {
+    pub fn new(tick_rate: u32) -> Self {
+        Self {
+            tick: 0,
+            tick_seconds: 1. / tick_rate.max(1) as f32,
+        }
+    }
+
+    pub fn advance(&mut self) {
+        self.tick += 1;
+    }
+
+    /// Game time in seconds at the current tick
+    pub fn elapsed(&self) -> f32 {
+        self.tick as f32 * self.tick_seconds
+    }
+}
+
+impl Default for SimClock {
+    fn default() -> Self {
+        Self::new(60)
+    }
+}
\ No newline at end of file
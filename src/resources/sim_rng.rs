@@ -0,0 +1,27 @@
+use rand::SeedableRng;
+use rand_pcg::Pcg64Mcg;
+use serde::{Deserialize, Serialize};
+use crate::config::Map as MapConfig;
+
+/// Random numbers for gameplay, seeded so a game can be played out again.
+/// The generator's state can be saved and picked up again exactly where it was
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SimRng {
+    /// Picked once when the game starts, the map is generated from it unless the map config has a seed
+    pub seed: u64,
+    pub rng: Pcg64Mcg,
+}
+
+impl SimRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: Pcg64Mcg::seed_from_u64(seed),
+        }
+    }
+
+    /// The seed to generate a map from with the given config
+    pub fn map_seed(&self, map_config: &MapConfig) -> u64 {
+        map_config.seed.unwrap_or(self.seed)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A sprite sheet image and the RON describing its sprites. The RON is loaded
+/// as the SpriteSheet and as a NamedSpriteSheet for looking sprites up by name
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SheetEntry {
+    pub texture: String,
+    pub sprites: String,
+}
+
+/// A character made from a sheet and an animation set, by their keys in the manifest
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CharacterEntry {
+    pub sheet: String,
+    pub animations: String,
+}
+
+/// Every sprite asset the game loads, by the keys gameplay code and configs use.
+/// Paths are relative to the assets directory
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SpriteManifest {
+    pub sheets: HashMap<String, SheetEntry>,
+    /// NamedAnimationSet RONs
+    pub animations: HashMap<String, String>,
+    pub characters: HashMap<String, CharacterEntry>,
+    /// Sheet the map tiles come from
+    pub tiles: String,
+    /// Character used for anything with a sprite that isn't in the manifest
+    pub default_character: String,
+}
\ No newline at end of file
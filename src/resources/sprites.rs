@@ -7,7 +7,7 @@ use amethyst::{
         Handle,
         Completion,
     },
-    ecs::prelude::{Builder, World, WorldExt},
+    ecs::prelude::{Builder, Entity, LazyUpdate, World, WorldExt},
     renderer::{
         ImageFormat, 
         SpriteRender, 
@@ -28,6 +28,7 @@ use amethyst::{
     },
 };
 use regex::Regex;
+use std::collections::HashMap;
 use rand::{
     thread_rng,
     seq::SliceRandom,
@@ -39,113 +40,94 @@ use crate::resources::{
     NamedSpriteSheetHandle,
     NamedAnimationSet,
     NamedAnimationSetHandle,
+    SheetEntry,
+    SpriteManifest,
 };
 
-const CHARACTER_SPRITE_SHEET_RON: &str = "sprite_sheets/character_0.ron";
-const CHARACTER_SPRITE_SHEET_PNG: &str = "sprite_sheets/character_0.png";
-const CHARACTER_ANIMATION_SET_RON: &str = "animations/character_0.ron";
-const TILESET_SPRITE_SHEET_RON: &str = "sprite_sheets/tiles.ron";
-const TILESET_SPRITE_SHEET_PNG: &str = "sprite_sheets/tiles.png";
-
-
 #[derive(Debug)]
 pub enum Error {
     AssetLoadingIncomplete,
+    /// The manifest refers to a sheet it doesn't list
+    UnknownSheet(String),
+    /// The manifest refers to an animation set it doesn't list
+    UnknownAnimationSet(String),
+    /// The manifest's default character isn't one of its characters
+    UnknownCharacter(String),
 }
 
-/// Holds handles to required assets
+/// Handles to the assets for one sheet
+struct SheetHandles {
+    named_sprites_handle: NamedSpriteSheetHandle,
+    sheet_handle: Handle<SpriteSheet>,
+}
+
+/// Holds handles to the assets in the SpriteManifest
 pub struct SpritesLoader {
-    character_named_sprites_handle: NamedSpriteSheetHandle,
-    character_named_animation_handle: NamedAnimationSetHandle,
-    character_sheet_handle: Handle<SpriteSheet>,
-    tiles_named_sprites_handle: NamedSpriteSheetHandle,
-    tiles_sheet_handle: Handle<SpriteSheet>,
+    manifest: SpriteManifest,
+    sheets: HashMap<String, SheetHandles>,
+    animations: HashMap<String, NamedAnimationSetHandle>,
     progress_counter: ProgressCounter,
 }
 
-impl SpritesLoader {
-    /// Creates a new SpritesLoader which kicks off loading of the assets required by Sprites
-    pub fn new(world: &mut World, mut progress_counter: ProgressCounter) -> Self {  
-        let character_named_sprites_handle = {
-            let loader = world.read_resource::<Loader>();
-            loader.load(
-                CHARACTER_SPRITE_SHEET_RON,
-                RonFormat,
-                &mut progress_counter,
-                &world.read_resource::<AssetStorage<NamedSpriteSheet>>(),
-            )
-        };  
-
-        let character_named_animation_handle = {
-            let loader = world.read_resource::<Loader>();
-            loader.load(
-                CHARACTER_ANIMATION_SET_RON,
-                RonFormat,
-                &mut progress_counter,
-                &world.read_resource::<AssetStorage<NamedAnimationSet>>(),
-            )
-        };
+fn load_sheet(world: &World, entry: &SheetEntry, progress_counter: &mut ProgressCounter) -> SheetHandles {
+    let loader = world.read_resource::<Loader>();
 
-        let texture_handle = {
-            let loader = world.read_resource::<Loader>();
-            let texture_storage = world.read_resource::<AssetStorage<Texture>>();
-            loader.load(
-                CHARACTER_SPRITE_SHEET_PNG,
-                ImageFormat::default(),
-                &mut progress_counter,
-                &texture_storage,
-            )
-        };
-
-        let character_sheet_handle = {
-            let loader = world.read_resource::<Loader>();
-            let sheet_storage = world.read_resource::<AssetStorage<SpriteSheet>>();
-            loader.load(
-                CHARACTER_SPRITE_SHEET_RON,
-                SpriteSheetFormat(texture_handle),
-                &mut progress_counter,
-                &sheet_storage,
-            )
-        };
+    let named_sprites_handle = loader.load(
+        entry.sprites.as_str(),
+        RonFormat,
+        &mut *progress_counter,
+        &world.read_resource::<AssetStorage<NamedSpriteSheet>>(),
+    );
+
+    let texture_handle = loader.load(
+        entry.texture.as_str(),
+        ImageFormat::default(),
+        &mut *progress_counter,
+        &world.read_resource::<AssetStorage<Texture>>(),
+    );
+
+    let sheet_handle = loader.load(
+        entry.sprites.as_str(),
+        SpriteSheetFormat(texture_handle),
+        &mut *progress_counter,
+        &world.read_resource::<AssetStorage<SpriteSheet>>(),
+    );
+
+    SheetHandles {
+        named_sprites_handle,
+        sheet_handle,
+    }
+}
 
-        let texture_handle = {
+impl SpritesLoader {
+    /// Creates a new SpritesLoader which kicks off loading of everything in the manifest
+    pub fn new(world: &mut World, manifest: SpriteManifest, mut progress_counter: ProgressCounter) -> Self {  
+        let sheets = manifest.sheets
+            .iter()
+            .map(|(key, entry)| (key.clone(), load_sheet(world, entry, &mut progress_counter)))
+            .collect();
+
+        let animations = {
             let loader = world.read_resource::<Loader>();
-            let texture_storage = world.read_resource::<AssetStorage<Texture>>();
-            loader.load(
-                TILESET_SPRITE_SHEET_PNG,
-                ImageFormat::default(),
-                &mut progress_counter,
-                &texture_storage,
-            )
+            let animation_set_storage = world.read_resource::<AssetStorage<NamedAnimationSet>>();
+            manifest.animations
+                .iter()
+                .map(|(key, path)| {
+                    let handle = loader.load(
+                        path.as_str(),
+                        RonFormat,
+                        &mut progress_counter,
+                        &animation_set_storage,
+                    );
+                    (key.clone(), handle)
+                })
+                .collect()
         };
 
-        let tiles_sheet_handle = {
-            let loader = world.read_resource::<Loader>();
-            let sheet_storage = world.read_resource::<AssetStorage<SpriteSheet>>();
-            loader.load(
-                TILESET_SPRITE_SHEET_RON,
-                SpriteSheetFormat(texture_handle),
-                &mut progress_counter,
-                &sheet_storage,
-            )
-        };  
-
-        let tiles_named_sprites_handle = {
-            let loader = world.read_resource::<Loader>();
-            loader.load(
-                TILESET_SPRITE_SHEET_RON,
-                RonFormat,
-                &mut progress_counter,
-                &world.read_resource::<AssetStorage<NamedSpriteSheet>>(),
-            )
-        };  
-
         Self {
-            character_named_sprites_handle,
-            character_named_animation_handle,
-            character_sheet_handle,
-            tiles_sheet_handle,
-            tiles_named_sprites_handle,
+            manifest,
+            sheets,
+            animations,
             progress_counter,
         }
     }
@@ -197,22 +179,108 @@ pub struct Tiles {
     floor: Vec<SpriteRender>,
 }
 
-fn collect_named_sprites(regex: &str, named_sprites: &NamedSpriteSheet, sprite_sheet: Handle<SpriteSheet>) -> Vec<SpriteRender> {
+/// A loaded sheet and the names of its sprites, in the order of their sprite numbers
+struct Sheet {
+    handle: Handle<SpriteSheet>,
+    names: Vec<String>,
+}
+
+impl Sheet {
+    fn sprite(&self, name: &str) -> Option<SpriteRender> {
+        self.names
+            .iter()
+            .position(|n| n == name)
+            .map(|i| SpriteRender {
+                sprite_sheet: self.handle.clone(),
+                sprite_number: i,
+            })
+    }
+}
+
+fn collect_named_sprites(regex: &str, sheet: &Sheet) -> Vec<SpriteRender> {
     let regex = Regex::new(regex).expect("regex failed to compile");
 
-    named_sprites
-        .sprites.iter().enumerate()
-        .filter(|(_, s)| regex.is_match(&s.name))
+    sheet
+        .names.iter().enumerate()
+        .filter(|(_, name)| regex.is_match(name))
         .map(|(i, _)| SpriteRender {
-            sprite_sheet: sprite_sheet.clone(),
+            sprite_sheet: sheet.handle.clone(),
             sprite_number: i,
         }).collect()
 }
 
+/// The still sprite and animations of a character
+struct Character {
+    sprite_render: SpriteRender,
+    animation_set: AnimationSet<AnimationId, SpriteRender>,
+}
+
+impl Character {
+    /// Builds the animations from the named frames in the sheet
+    fn new(world: &World, key: &str, sheet: &Sheet, named_animation_handle: &NamedAnimationSetHandle) -> Self {
+        let animation_set_storage = world.read_resource::<AssetStorage<NamedAnimationSet>>();
+        let named_animations = animation_set_storage
+            .get(named_animation_handle)
+            .expect(&format!("NamedAnimationSet {:?} missing in Sprites::new", named_animation_handle));
+
+        let mut sampler_storage = world.write_resource::<AssetStorage<Sampler<SpriteRenderPrimitive>>>();
+        let mut animation_storage = world.write_resource::<AssetStorage<Animation<SpriteRender>>>();
+
+        let mut animation_set = AnimationSet::new();
+        for anim in named_animations.animations.iter() {
+            assert_eq!(anim.input.len(), anim.output.len(), 
+                "Animation input and output vectors must have the same length");
+
+            let mut sampler: Sampler<SpriteRenderPrimitive> = Sampler {
+                input: anim.input.clone(),
+                output: Vec::new(),
+                function: InterpolationFunction::Step,
+            };
+
+            for name in anim.output.iter() {
+                let frame = sheet
+                    .names
+                    .iter()
+                    .position(|x| x == name)
+                    .expect(&format!("Failed to find frame named {} for {}", name, key));
+                let sprite_index = SpriteRenderPrimitive::SpriteIndex(frame);
+                sampler.output.push(sprite_index);
+            }
+            let sampler_handle = sampler_storage.insert(sampler);
+
+            let animation_handle = animation_storage.insert(Animation {
+                nodes: vec![
+                    (0, SpriteRenderChannel::SpriteIndex, sampler_handle),
+                ],
+            });
+
+            animation_set.animations.insert(anim.id, animation_handle);
+        }
+
+        Self {
+            sprite_render: SpriteRender {
+                sprite_sheet: sheet.handle.clone(),
+                sprite_number: 0,
+            },
+            animation_set,
+        }
+    }
+
+    fn components(&self) -> AnimatedSpriteComponents {
+        AnimatedSpriteComponents {
+            sprite_render: self.sprite_render.clone(),
+            animation_set: self.animation_set.clone(),
+            control_set: AnimationControlSet::default(),
+            default_animation: None
+        }
+    }
+}
+
 /// Holds handles to loaded assets for sprites and animations
 pub struct Sprites {
-    character_1_sprite_render: SpriteRender,
-    character_1_animation_set: AnimationSet<AnimationId, SpriteRender>,
+    sheets: HashMap<String, Sheet>,
+    characters: HashMap<String, Character>,
+    default_character: String,
     tiles: Tiles,
 }
 
@@ -223,107 +291,87 @@ impl Sprites {
             return Err(Error::AssetLoadingIncomplete);
         }
 
-        let SpritesLoader {
-            character_named_sprites_handle,
-            character_named_animation_handle,
-            character_sheet_handle,
-            tiles_sheet_handle,
-            tiles_named_sprites_handle,
-            ..
-        } = sprites_loader;
-
-        let mut character_1_animation_set = AnimationSet::new();
-        {
+        let manifest = &sprites_loader.manifest;
+
+        let sheets: HashMap<String, Sheet> = {
             let named_sprites_storage = world.read_resource::<AssetStorage<NamedSpriteSheet>>();
-            let named_sprites = named_sprites_storage
-                .get(&character_named_sprites_handle)
-                .expect(&format!("NamedSpriteSheet {:?} missing in Sprites::new", character_named_sprites_handle));
+            sprites_loader.sheets
+                .iter()
+                .map(|(key, handles)| {
+                    let named_sprites = named_sprites_storage
+                        .get(&handles.named_sprites_handle)
+                        .expect(&format!("NamedSpriteSheet {:?} missing in Sprites::new", handles.named_sprites_handle));
+                    let sheet = Sheet {
+                        handle: handles.sheet_handle.clone(),
+                        names: named_sprites.sprites.iter().map(|s| s.name.clone()).collect(),
+                    };
+                    (key.clone(), sheet)
+                })
+                .collect()
+        };
 
-            let animation_set_storage = world.read_resource::<AssetStorage<NamedAnimationSet>>();
-            let named_animations = animation_set_storage
-                .get(&character_named_animation_handle)
-                .expect(&format!("NamedAnimationSet {:?} missing in Sprites::new", character_named_animation_handle));
-
-            let mut sampler_storage = world.write_resource::<AssetStorage<Sampler<SpriteRenderPrimitive>>>();
-            let mut animation_storage = world.write_resource::<AssetStorage<Animation<SpriteRender>>>();
-
-            for anim in named_animations.animations.iter() {
-                assert_eq!(anim.input.len(), anim.output.len(), 
-                    "Animation input and output vectors must have the same length");
-
-                let mut sampler: Sampler<SpriteRenderPrimitive> = Sampler {
-                    input: anim.input.clone(),
-                    output: Vec::new(),
-                    function: InterpolationFunction::Step,
-                };
-
-                for name in anim.output.iter() {
-                    let frame = named_sprites
-                        .sprites
-                        .iter()
-                        .position(|x| &x.name == name)
-                        .expect(&format!("Failed to find frame named {}", name));
-                    let sprite_index = SpriteRenderPrimitive::SpriteIndex(frame);
-                    sampler.output.push(sprite_index);
-                }
-                let sampler_handle = sampler_storage.insert(sampler);
-
-                let animation_handle = animation_storage.insert(Animation {
-                    nodes: vec![
-                        (0, SpriteRenderChannel::SpriteIndex, sampler_handle),
-                    ],
-                });
-
-                character_1_animation_set.animations.insert(anim.id, animation_handle);
-            }
+        let mut characters = HashMap::new();
+        for (key, entry) in manifest.characters.iter() {
+            let sheet = sheets
+                .get(&entry.sheet)
+                .ok_or_else(|| Error::UnknownSheet(entry.sheet.clone()))?;
+            let named_animation_handle = sprites_loader.animations
+                .get(&entry.animations)
+                .ok_or_else(|| Error::UnknownAnimationSet(entry.animations.clone()))?;
+            characters.insert(key.clone(), Character::new(world, key, sheet, named_animation_handle));
         }
 
-        let character_1_sprite_render = SpriteRender {
-            sprite_sheet: character_sheet_handle.clone(),
-            sprite_number: 0,
-        };
+        if !characters.contains_key(&manifest.default_character) {
+            return Err(Error::UnknownCharacter(manifest.default_character.clone()));
+        }
 
         let tiles = {
-            let named_sprites_storage = world.read_resource::<AssetStorage<NamedSpriteSheet>>();
-            let named_sprites = named_sprites_storage
-                .get(&tiles_named_sprites_handle)
-                .expect(&format!("NamedSpriteSheet {:?} missing in Sprites::new", tiles_named_sprites_handle));
+            let sheet = sheets
+                .get(&manifest.tiles)
+                .ok_or_else(|| Error::UnknownSheet(manifest.tiles.clone()))?;
 
             Tiles {
-                east: collect_named_sprites(r"dungeon_walls_east_\d+", &named_sprites, tiles_sheet_handle.clone()),
-                inner_corner_north_east: collect_named_sprites(r"dungeon_walls_inner_corner_north_east_\d+", &named_sprites, tiles_sheet_handle.clone()),
-                inner_corner_north_west: collect_named_sprites(r"dungeon_walls_inner_corner_north_west_\d+", &named_sprites, tiles_sheet_handle.clone()),
-                inner_corner_south_east: collect_named_sprites(r"dungeon_walls_inner_corner_south_east_\d+", &named_sprites, tiles_sheet_handle.clone()),
-                inner_corner_south_west: collect_named_sprites(r"dungeon_walls_inner_corner_south_west_\d+", &named_sprites, tiles_sheet_handle.clone()),
-                north: collect_named_sprites(r"dungeon_walls_north_\d+", &named_sprites, tiles_sheet_handle.clone()),
-                outer_corner_north_east: collect_named_sprites(r"dungeon_walls_outer_corner_north_east_\d+", &named_sprites, tiles_sheet_handle.clone()),
-                outer_corner_north_west: collect_named_sprites(r"dungeon_walls_outer_corner_north_west_\d+", &named_sprites, tiles_sheet_handle.clone()),
-                outer_corner_south_east: collect_named_sprites(r"dungeon_walls_outer_corner_south_east_\d+", &named_sprites, tiles_sheet_handle.clone()),
-                outer_corner_south_west: collect_named_sprites(r"dungeon_walls_outer_corner_south_west_\d+", &named_sprites, tiles_sheet_handle.clone()),
-                solid: collect_named_sprites(r"dungeon_walls_solid_\d+", &named_sprites, tiles_sheet_handle.clone()),
-                south: collect_named_sprites(r"dungeon_walls_south_\d+", &named_sprites, tiles_sheet_handle.clone()),  
-                west: collect_named_sprites(r"dungeon_walls_west_\d+", &named_sprites, tiles_sheet_handle.clone()),       
-                blob: collect_named_sprites(r"blob_\d+", &named_sprites, tiles_sheet_handle.clone()),    
-                floor: collect_named_sprites(r"dungeon_tiles_\d+", &named_sprites, tiles_sheet_handle.clone()),    
+                east: collect_named_sprites(r"dungeon_walls_east_\d+", sheet),
+                inner_corner_north_east: collect_named_sprites(r"dungeon_walls_inner_corner_north_east_\d+", sheet),
+                inner_corner_north_west: collect_named_sprites(r"dungeon_walls_inner_corner_north_west_\d+", sheet),
+                inner_corner_south_east: collect_named_sprites(r"dungeon_walls_inner_corner_south_east_\d+", sheet),
+                inner_corner_south_west: collect_named_sprites(r"dungeon_walls_inner_corner_south_west_\d+", sheet),
+                north: collect_named_sprites(r"dungeon_walls_north_\d+", sheet),
+                outer_corner_north_east: collect_named_sprites(r"dungeon_walls_outer_corner_north_east_\d+", sheet),
+                outer_corner_north_west: collect_named_sprites(r"dungeon_walls_outer_corner_north_west_\d+", sheet),
+                outer_corner_south_east: collect_named_sprites(r"dungeon_walls_outer_corner_south_east_\d+", sheet),
+                outer_corner_south_west: collect_named_sprites(r"dungeon_walls_outer_corner_south_west_\d+", sheet),
+                solid: collect_named_sprites(r"dungeon_walls_solid_\d+", sheet),
+                south: collect_named_sprites(r"dungeon_walls_south_\d+", sheet),  
+                west: collect_named_sprites(r"dungeon_walls_west_\d+", sheet),       
+                blob: collect_named_sprites(r"blob_\d+", sheet),    
+                floor: collect_named_sprites(r"dungeon_tiles_\d+", sheet),    
             }
         };
 
         log::info!("{:#?}", tiles);
 
         Ok(Self {
-            character_1_sprite_render,
-            character_1_animation_set,
+            sheets,
+            characters,
+            default_character: manifest.default_character.clone(),
             tiles,
         })
     }
 
-    pub fn get_character_1_components(&self) -> AnimatedSpriteComponents {
-        AnimatedSpriteComponents {
-            sprite_render: self.character_1_sprite_render.clone(),
-            animation_set: self.character_1_animation_set.clone(),
-            control_set: AnimationControlSet::default(),
-            default_animation: None
-        }
+    /// The components for the manifest's default character, for things with a missing sprite
+    pub fn get_default_character_components(&self) -> AnimatedSpriteComponents {
+        self.characters[&self.default_character].components()
+    }
+
+    /// Looks up animated sprite components by character name
+    pub fn get_animated_sprite_components(&self, name: &str) -> Option<AnimatedSpriteComponents> {
+        self.characters.get(name).map(Character::components)
+    }
+
+    /// Looks up a single sprite by its name in a sheet
+    pub fn get_sprite(&self, sheet: &str, name: &str) -> Option<SpriteRender> {
+        self.sheets.get(sheet)?.sprite(name)
     }
 
     pub fn get_tile(&self, tile: TileDirection) -> SpriteRender {
@@ -375,6 +423,24 @@ impl AnimatedSpriteComponents {
     where
         B: Builder
     {
+        self.start_default_animation();
+
+        builder
+            .with(self.sprite_render)
+            .with(self.animation_set)
+            .with(self.control_set)
+    }
+
+    /// Replaces the sprite and animations of an existing entity
+    pub fn insert(mut self, entity: Entity, lazy_update: &LazyUpdate) {
+        self.start_default_animation();
+
+        lazy_update.insert(entity, self.sprite_render);
+        lazy_update.insert(entity, self.animation_set);
+        lazy_update.insert(entity, self.control_set);
+    }
+
+    fn start_default_animation(&mut self) {
         if let Some(default_animation) = self.default_animation.take() {
             let anim = self.animation_set.get(&default_animation).expect("AnimatedSpriteComponents.default_animation was missing from animation set");
             self.control_set.add_animation(
@@ -385,10 +451,5 @@ impl AnimatedSpriteComponents {
                 AnimationCommand::Start,
             );
         }
-
-        builder
-            .with(self.sprite_render)
-            .with(self.animation_set)
-            .with(self.control_set)
     }
 }
\ No newline at end of file
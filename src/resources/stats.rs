@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+/// Running totals for the game so far
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Stats {
+    pub kills: u32,
+    /// Creeps that reached the exit
+    pub leaks: u32,
+    pub towers_built: u32,
+    pub towers_sold: u32,
+    pub tower_upgrades: u32,
+    /// Gold from bounties and selling towers
+    pub gold_earned: u32,
+    /// Gold spent on towers and upgrades
+    pub gold_spent: u32,
+}
\ No newline at end of file
@@ -0,0 +1,112 @@
+const SPEEDS: [u32; 3] = [1, 2, 4];
+
+/// How many simulation ticks Running runs per fixed update
+#[derive(Debug, Clone, Copy)]
+pub struct TimeScale {
+    pub paused: bool,
+    /// Ticks per fixed update when not paused
+    pub speed: u32,
+    /// Ticks left to run while paused
+    pub steps: u32,
+}
+
+impl Default for TimeScale {
+    fn default() -> Self {
+        Self {
+            paused: false,
+            speed: 1,
+            steps: 0,
+        }
+    }
+}
+
+impl TimeScale {
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+        self.steps = 0;
+    }
+
+    /// Goes through 1x, 2x and 4x in turn
+    pub fn cycle_speed(&mut self) {
+        let i = SPEEDS.iter().position(|s| *s == self.speed).unwrap_or(0);
+        self.speed = SPEEDS[(i + 1) % SPEEDS.len()];
+    }
+
+    /// Runs a single tick, only while paused
+    pub fn step(&mut self) {
+        if self.paused {
+            self.steps += 1;
+        }
+    }
+
+    /// Ticks to run this fixed update, using up any steps
+    pub fn take_ticks(&mut self) -> u32 {
+        if self.paused {
+            let steps = self.steps;
+            self.steps = 0;
+            steps
+        } else {
+            self.speed
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runs_speed_ticks_when_not_paused() {
+        let mut time_scale = TimeScale::default();
+        assert_eq!(time_scale.take_ticks(), 1);
+        time_scale.cycle_speed();
+        assert_eq!(time_scale.take_ticks(), 2);
+        assert_eq!(time_scale.take_ticks(), 2);
+    }
+
+    #[test]
+    fn speeds_cycle_back_to_one() {
+        let mut time_scale = TimeScale::default();
+        let speeds: Vec<_> = (0..4).map(|_| {
+            time_scale.cycle_speed();
+            time_scale.speed
+        }).collect();
+        assert_eq!(speeds, vec!(2, 4, 1, 2));
+    }
+
+    #[test]
+    fn paused_runs_no_ticks() {
+        let mut time_scale = TimeScale::default();
+        time_scale.cycle_speed();
+        time_scale.toggle_pause();
+        assert_eq!(time_scale.take_ticks(), 0);
+    }
+
+    #[test]
+    fn steps_are_used_up_once() {
+        let mut time_scale = TimeScale::default();
+        time_scale.toggle_pause();
+        time_scale.step();
+        time_scale.step();
+        assert_eq!(time_scale.take_ticks(), 2);
+        assert_eq!(time_scale.take_ticks(), 0);
+    }
+
+    #[test]
+    fn steps_are_ignored_while_running() {
+        let mut time_scale = TimeScale::default();
+        time_scale.step();
+        assert_eq!(time_scale.take_ticks(), 1);
+        time_scale.toggle_pause();
+        assert_eq!(time_scale.take_ticks(), 0);
+    }
+
+    #[test]
+    fn unpausing_drops_leftover_steps() {
+        let mut time_scale = TimeScale::default();
+        time_scale.toggle_pause();
+        time_scale.step();
+        time_scale.toggle_pause();
+        assert_eq!(time_scale.take_ticks(), 1);
+    }
+}
\ No newline at end of file
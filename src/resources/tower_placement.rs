@@ -0,0 +1,12 @@
+use crate::components::map::PlacementError;
+
+/// What the player is about to build and where
+#[derive(Debug, Default)]
+pub struct TowerPlacement {
+    /// Index of the selected archetype in the tower config
+    pub archetype: usize,
+    /// The cell under the cursor
+    pub cell: Option<(usize, usize)>,
+    /// Why a tower can't be built in cell
+    pub error: Option<PlacementError>,
+}
\ No newline at end of file
@@ -0,0 +1,83 @@
+use amethyst::ecs::Entity;
+use std::collections::{HashMap, HashSet};
+use crate::components::Map;
+
+/// The cells a tower can see from its cell
+pub struct VisibleCells {
+    /// Range the cells were gathered for
+    pub range: f32,
+    pub cells: HashSet<(usize, usize)>,
+}
+
+impl VisibleCells {
+    pub fn new(map: &Map, origin: (usize, usize), range: f32) -> Self {
+        // Pad by a cell diagonal so creeps in range but part way across a
+        // cell further out aren't missed
+        let reach = range + 2_f32.sqrt();
+        let r = reach.ceil() as isize;
+        let (ox, oy) = (origin.0 as isize, origin.1 as isize);
+
+        let mut cells = HashSet::new();
+        for x in (ox - r).max(0)..=(ox + r).min(map.width() as isize - 1) {
+            for y in (oy - r).max(0)..=(oy + r).min(map.height() as isize - 1) {
+                let (dx, dy) = ((x - ox) as f32, (y - oy) as f32);
+                if (dx * dx + dy * dy).sqrt() > reach { continue }
+
+                let cell = (x as usize, y as usize);
+                if map.line_of_sight(origin, cell) {
+                    cells.insert(cell);
+                }
+            }
+        }
+
+        Self {
+            range,
+            cells,
+        }
+    }
+
+    pub fn can_see(&self, cell: (usize, usize)) -> bool {
+        self.cells.contains(&cell)
+    }
+}
+
+/// Line of sight from each tower cell, kept until the map's walls change
+#[derive(Default)]
+pub struct Visibility {
+    /// The map entity and seed the sets were built from. Towers being placed
+    /// or sold doesn't block sight so Map::version isn't part of this
+    built_from: Option<(Entity, u64)>,
+    towers: HashMap<(usize, usize), VisibleCells>,
+}
+
+impl Visibility {
+    pub fn is_current(&self, map_entity: Entity, map: &Map) -> bool {
+        self.built_from == Some((map_entity, map.seed()))
+    }
+
+    /// Throws away every set, they're rebuilt as towers ask for them
+    pub fn reset(&mut self, map_entity: Entity, map: &Map) {
+        self.towers.clear();
+        self.built_from = Some((map_entity, map.seed()));
+    }
+
+    /// Builds the set for a tower cell if it's missing or was built for a different range
+    pub fn update(&mut self, map: &Map, cell: (usize, usize), range: f32) {
+        let stale = self.towers.get(&cell).map_or(true, |v| v.range != range);
+        if stale {
+            self.towers.insert(cell, VisibleCells::new(map, cell, range));
+        }
+    }
+
+    /// Drops sets for cells that no longer have a tower
+    pub fn retain<F>(&mut self, mut keep: F) 
+    where
+        F: FnMut((usize, usize)) -> bool
+    {
+        self.towers.retain(|cell, _| keep(*cell));
+    }
+
+    pub fn get(&self, cell: (usize, usize)) -> Option<&VisibleCells> {
+        self.towers.get(&cell)
+    }
+}
\ No newline at end of file
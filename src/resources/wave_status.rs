@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+/// Progress through the waves in waves.ron
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct WaveStatus {
+    /// 1 based number of the latest wave to start, 0 before the first one starts
+    pub wave: usize,
+    pub wave_count: usize,
+    /// Seconds until the next wave starts if one is counting down
+    pub next_wave_in: Option<f32>,
+    /// Set once every wave has spawned and all the creeps are gone
+    pub cleared: bool,
+}
+
+/// Spawning progress of a single WaveEntry
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct EntryProgress {
+    pub spawned: usize,
+    pub elapsed: f32,
+}
+
+/// Where the Spawner system is up to in waves.ron
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct WaveProgress {
+    /// Index of the wave that is counting down or spawning
+    pub wave_i: usize,
+    /// Time left before wave_i starts
+    pub countdown: Option<f32>,
+    /// One per entry in wave_i while it is spawning
+    pub entries: Vec<EntryProgress>,
+    pub spawning: bool,
+}
\ No newline at end of file
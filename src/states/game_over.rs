@@ -0,0 +1,37 @@
+use amethyst::{
+    input::{
+        is_close_requested, 
+        is_key_down, 
+        VirtualKeyCode, 
+    },
+    prelude::*,
+};
+
+#[derive(Default)]
+pub struct GameOver;
+
+impl SimpleState for GameOver {
+    fn on_start(&mut self, _data: StateData<'_, GameData<'_, '_>>) {
+        log::info!("Game over! Press Escape or Q to quit");
+    }
+
+    fn handle_event(
+        &mut self,
+        _data: StateData<'_, GameData<'_, '_>>,
+        event: StateEvent,
+    ) -> SimpleTrans {
+        match &event {
+            StateEvent::Window(event) => {
+                if is_close_requested(&event) || 
+                   is_key_down(&event, VirtualKeyCode::Escape) || 
+                   is_key_down(&event, VirtualKeyCode::Q) 
+                {
+                    Trans::Quit
+                } else {
+                    Trans::None
+                }
+            },
+            _ => Trans::None,
+        }
+    }
+}
\ No newline at end of file
@@ -4,15 +4,20 @@ use amethyst::{
         ProgressCounter,
         Completion,
     },
+    utils::application_root_dir,
 };
 use crate::{
     resources::{
         Sprites,
         SpritesLoader,
+        SpriteManifest,
     },
-    states::Running,
+    states::Menu,
 };
 
+/// Lists the sprite assets to load, relative to the assets directory
+const SPRITE_MANIFEST: &str = "sprites.ron";
+
 #[derive(Default)]
 pub struct Loading {
     sprites_loader: Option<SpritesLoader>,
@@ -22,8 +27,17 @@ impl SimpleState for Loading {
     fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
         let world = data.world;     
 
-        let sprites_loader = SpritesLoader::new(world, ProgressCounter::default());
-        self.sprites_loader = Some(sprites_loader);
+        let manifest_path = application_root_dir()
+            .expect("Failed to find the application root")
+            .join("assets")
+            .join(SPRITE_MANIFEST);
+        match SpriteManifest::load_no_fallback(&manifest_path) {
+            Ok(manifest) => {
+                let sprites_loader = SpritesLoader::new(world, manifest, ProgressCounter::default());
+                self.sprites_loader = Some(sprites_loader);
+            },
+            Err(e) => log::error!("Failed to load sprite manifest {:?}: {}", manifest_path, e),
+        }
     }
 
      fn update(&mut self, data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
@@ -34,13 +48,17 @@ impl SimpleState for Loading {
                     log::info!("Asset loading complete.");
 
                     // Create the sprites resource now that it's assets are loaded
-                    let sprites = Sprites::new(&mut data.world, sprites_loader)
-                        .expect("Failed to create Sprites after loading complete");
-                        
-                    // Insert it into the world
-                    data.world.insert(sprites);
-                    
-                    Trans::Switch(Box::new(Running::default()))
+                    match Sprites::new(&mut data.world, sprites_loader) {
+                        Ok(sprites) => {
+                            // Insert it into the world
+                            data.world.insert(sprites);
+                            Trans::Switch(Box::new(Menu))
+                        },
+                        Err(e) => {
+                            log::error!("Failed to create Sprites after loading complete: {:?}", e);
+                            Trans::Quit
+                        },
+                    }
                 },
                 Completion::Failed => {
                     log::error!("Failed to load assets, exiting");
@@ -0,0 +1,59 @@
+use amethyst::{
+    input::{
+        is_close_requested, 
+        is_key_down, 
+        VirtualKeyCode, 
+    },
+    prelude::*,
+};
+use crate::{
+    states::Running,
+    util::save::{
+        quicksave_path,
+        SaveGame,
+    },
+};
+
+#[derive(Default)]
+pub struct Menu;
+
+impl SimpleState for Menu {
+    fn on_start(&mut self, _data: StateData<'_, GameData<'_, '_>>) {
+        log::info!("Press N for a new game, L to load the quicksave or Escape to quit");
+    }
+
+    fn handle_event(
+        &mut self,
+        data: StateData<'_, GameData<'_, '_>>,
+        event: StateEvent,
+    ) -> SimpleTrans {
+        match &event {
+            StateEvent::Window(event) => {
+                if is_close_requested(&event) || 
+                   is_key_down(&event, VirtualKeyCode::Escape) || 
+                   is_key_down(&event, VirtualKeyCode::Q) 
+                {
+                    Trans::Quit
+                } else if is_key_down(&event, VirtualKeyCode::N) {
+                    Trans::Switch(Box::new(Running::default()))
+                } else if is_key_down(&event, VirtualKeyCode::L) {
+                    let path = quicksave_path();
+                    match SaveGame::read(&path) {
+                        Ok(save) => {
+                            save.restore(data.world);
+                            log::info!("Loaded {:?}", path);
+                            Trans::Switch(Box::new(Running::default()))
+                        },
+                        Err(e) => {
+                            log::warn!("Failed to load {:?}: {}", path, e);
+                            Trans::None
+                        },
+                    }
+                } else {
+                    Trans::None
+                }
+            },
+            _ => Trans::None,
+        }
+    }
+}
\ No newline at end of file
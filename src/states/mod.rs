@@ -2,4 +2,10 @@ mod running;
 pub use running::*;
 
 mod loading;
-pub use loading::*;
\ No newline at end of file
+pub use loading::*;
+
+mod game_over;
+pub use game_over::*;
+
+mod menu;
+pub use menu::*;
\ No newline at end of file
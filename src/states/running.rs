@@ -1,39 +1,142 @@
 use amethyst::{
-    core::transform::Transform,
+    core::{
+        transform::Transform,
+        math::Vector2,
+        timing::Time,
+    },
+    ecs::prelude::{Entity, Join},
     input::{
         is_close_requested, 
         is_key_down, 
         VirtualKeyCode, 
         InputEvent,
+        InputHandler,
         ScrollDirection,
+        StringBindings,
     },
     prelude::*,
-    renderer::Camera,
+    renderer::{
+        Camera,
+        SpriteRender,
+        Transparent,
+        palette::Srgba,
+        resources::Tint,
+    },
     window::ScreenDimensions,
     controls::WindowFocus,
     // Why is this located here? Import direct from winnit instead?
     renderer::rendy::wsi::winit::MouseButton,
+    winit::Event,
 };
 use crate::{
-    resources::{Zoom, CameraMove},
-    util::constants::CAMERA_Z,
+    components::{
+        Dying,
+        Map,
+        Navigator,
+        Tower,
+        map::PlacementError,
+    },
+    config::Tower as TowerConfig,
+    resources::{
+        Zoom,
+        CameraMove,
+        Lives,
+        Sprites,
+        Command,
+        Commands,
+        Gold,
+        Selection,
+        TowerPlacement,
+        TimeScale,
+    },
+    states::GameOver,
+    util::{
+        constants::{CAMERA_Z, CHARACTER_Z_OFFSET},
+        iso_to_screen,
+        screen_to_world,
+        Simulation,
+        blend_tick_positions,
+        record_tick_positions,
+        restore_tick_positions,
+        save::{
+            quicksave_path,
+            SaveGame,
+        },
+        replay,
+    },
 };
 
 const ZOOM_MAX: f32 = 2.5;
 const ZOOM_MIN: f32 = 0.3;
 
+const ARCHETYPE_KEYS: [VirtualKeyCode; 9] = [
+    VirtualKeyCode::Key1,
+    VirtualKeyCode::Key2,
+    VirtualKeyCode::Key3,
+    VirtualKeyCode::Key4,
+    VirtualKeyCode::Key5,
+    VirtualKeyCode::Key6,
+    VirtualKeyCode::Key7,
+    VirtualKeyCode::Key8,
+    VirtualKeyCode::Key9,
+];
+
 #[derive(Default)]
 pub struct Running {
     middle_mouse_down: bool,
+    camera: Option<Entity>,
+    /// Preview of the tower that will be placed under the cursor
+    ghost: Option<Entity>,
+    /// Runs the gameplay systems once per fixed tick
+    simulation: Option<Simulation>,
+    /// The last map check for the ghost tower, keyed by the cell, archetype, map entity and Map::version
+    /// it was made for as the reachability search is too slow to run every frame
+    placement_check: Option<(PlacementKey, Option<PlacementError>)>,
 }
 
+type PlacementKey = ((usize, usize), usize, Entity, u32);
+
 impl SimpleState for Running {
     fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
         let world = data.world;
-        init_camera(world);  
+        world.insert(TowerPlacement::default());
+        world.insert(Selection::default());
+        world.insert(TimeScale::default());
+        self.camera = Some(init_camera(world));  
+        self.ghost = init_ghost(world);
+        self.simulation = Some(Simulation::new(world));
         log::info!("Running");
     }
 
+    fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        replay::write_recording(data.world);
+    }
+
+    fn fixed_update(&mut self, data: StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
+        if let Some(simulation) = self.simulation.as_mut() {
+            // Paused fixed updates record the same positions again so nothing drifts
+            restore_tick_positions(data.world);
+            let ticks = data.world.write_resource::<TimeScale>().take_ticks();
+            for _ in 0..ticks {
+                simulation.tick(data.world);
+            }
+            record_tick_positions(data.world);
+        }
+        Trans::None
+    }
+
+    fn update(&mut self, data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
+        let alpha = data.world.read_resource::<Time>().interpolation_alpha();
+        blend_tick_positions(data.world, alpha);
+        self.update_placement(data.world);
+
+        if data.world.read_resource::<Lives>().lives == 0 {
+            Trans::Switch(Box::new(GameOver))
+        } else {
+            Trans::None
+        }
+    }
+
     fn handle_event(
         &mut self,
         data: StateData<'_, GameData<'_, '_>>,
@@ -47,6 +150,41 @@ impl SimpleState for Running {
                 {
                     Trans::Quit
                 } else {
+                    if let Some(archetype) = archetype_key(&event) {
+                        self.select_archetype(data.world, archetype);
+                    }
+                    if is_key_down(&event, VirtualKeyCode::X) {
+                        sell_selected(data.world);
+                    }
+                    if is_key_down(&event, VirtualKeyCode::U) {
+                        upgrade_selected(data.world);
+                    }
+                    if is_key_down(&event, VirtualKeyCode::T) {
+                        cycle_selected_targeting(data.world);
+                    }
+                    if is_key_down(&event, VirtualKeyCode::Return) {
+                        data.world.write_resource::<Commands>().push(Command::StartWave);
+                    }
+                    if is_key_down(&event, VirtualKeyCode::Space) {
+                        let mut time_scale = data.world.write_resource::<TimeScale>();
+                        time_scale.toggle_pause();
+                        log::info!("{}", if time_scale.paused { "Paused" } else { "Unpaused" });
+                    }
+                    if is_key_down(&event, VirtualKeyCode::Tab) {
+                        let mut time_scale = data.world.write_resource::<TimeScale>();
+                        time_scale.cycle_speed();
+                        log::info!("Speed {}x", time_scale.speed);
+                    }
+                    if is_key_down(&event, VirtualKeyCode::Period) {
+                        data.world.write_resource::<TimeScale>().step();
+                    }
+                    if is_key_down(&event, VirtualKeyCode::F5) {
+                        restore_tick_positions(data.world);
+                        save_game(data.world);
+                    }
+                    if is_key_down(&event, VirtualKeyCode::F9) {
+                        load_game(data.world);
+                    }
                     Trans::None
                 }
             },
@@ -63,6 +201,8 @@ impl SimpleState for Running {
                         },
                         InputEvent::MouseButtonPressed(MouseButton::Middle) => self.middle_mouse_down = true,
                         InputEvent::MouseButtonReleased(MouseButton::Middle) => self.middle_mouse_down = false,
+                        InputEvent::MouseButtonPressed(MouseButton::Left) => select_or_place(data.world),
+                        InputEvent::MouseButtonPressed(MouseButton::Right) => data.world.write_resource::<Selection>().cell = None,
                         InputEvent::MouseMoved { delta_x, delta_y } => if self.middle_mouse_down {
                             let mut camera_move = data.world.write_resource::<CameraMove>();
                             // I don't know why x is switched but there we are
@@ -79,7 +219,220 @@ impl SimpleState for Running {
     }
 }
 
-fn init_camera(world: &mut World) {
+impl Running {
+    /// Finds the map cell under the mouse cursor
+    fn cursor_cell(&self, world: &World) -> Option<(usize, usize)> {
+        let (mouse_x, mouse_y) = world.read_resource::<InputHandler<StringBindings>>().mouse_position()?;
+        let screen_size = {
+            let dimensions = world.read_resource::<ScreenDimensions>();
+            Vector2::new(dimensions.width(), dimensions.height())
+        };
+        let zoom = world.read_resource::<Zoom>().zoom;
+
+        let transforms = world.read_storage::<Transform>();
+        let camera = transforms.get(self.camera?)?;
+        let pos = screen_to_world(Vector2::new(mouse_x, mouse_y), screen_size, camera.translation(), zoom);
+
+        let maps = world.read_storage::<Map>();
+        let map = (&maps).join().next()?;
+        map.world_to_cell_index(pos)
+    }
+
+    /// Tracks the cell under the cursor and moves the ghost tower there
+    fn update_placement(&mut self, world: &mut World) {
+        let cell = self.cursor_cell(world);
+        let archetype = world.read_resource::<TowerPlacement>().archetype;
+        let error = cell.and_then(|cell| {
+            let entities = world.entities();
+            let maps = world.read_storage::<Map>();
+            let (map_entity, map) = (&entities, &maps).join().next()?;
+            let key = (cell, archetype, map_entity, map.version());
+            match self.placement_check {
+                Some((checked, error)) if checked == key => error,
+                _ => {
+                    let error = map.can_place_tower(cell).err();
+                    self.placement_check = Some((key, error));
+                    error
+                },
+            }
+        }).or_else(|| {
+            // Creeps move every tick so they're checked every frame too
+            let cell = cell?;
+            let navigators = world.read_storage::<Navigator>();
+            let dying = world.read_storage::<Dying>();
+            if (&navigators, !&dying).join().any(|(n, _)| n.is_in(cell)) {
+                Some(PlacementError::CreepInTheWay)
+            } else {
+                None
+            }
+        }).or_else(|| {
+            // Gold changes every tick so affordability is checked every frame
+            cell?;
+            let cost = world.read_resource::<TowerConfig>().archetypes.get(archetype)?.cost;
+            if world.read_resource::<Gold>().gold < cost {
+                Some(PlacementError::CantAfford)
+            } else {
+                None
+            }
+        });
+
+        {
+            let mut placement = world.write_resource::<TowerPlacement>();
+            placement.cell = cell;
+            placement.error = error;
+        }
+
+        if let Some(ghost) = self.ghost {
+            let color = match (cell, error) {
+                (None, _) => Srgba::new(1., 1., 1., 0.),
+                (Some(_), None) => Srgba::new(0.5, 1., 0.5, 0.6),
+                (Some(_), Some(_)) => Srgba::new(1., 0.3, 0.3, 0.6),
+            };
+            if let Some(tint) = world.write_storage::<Tint>().get_mut(ghost) {
+                tint.0 = color;
+            }
+
+            if let Some((x, y)) = cell {
+                if let Some(transform) = world.write_storage::<Transform>().get_mut(ghost) {
+                    let mut screen_pos = iso_to_screen(Vector2::new(x as f32, y as f32));
+                    screen_pos.z += CHARACTER_Z_OFFSET;
+                    transform.set_translation(screen_pos);
+                }
+            }
+        }
+    }
+
+    fn select_archetype(&self, world: &mut World, archetype: usize) {
+        let sprite = match archetype_sprite(world, archetype) {
+            Some(sprite) => sprite,
+            None => return,
+        };
+
+        world.write_resource::<TowerPlacement>().archetype = archetype;
+        if let Some(ghost) = self.ghost {
+            world.write_storage::<SpriteRender>()
+                .insert(ghost, sprite)
+                .expect("Failed to insert SpriteRender component");
+        }
+    }
+}
+
+fn archetype_key(event: &Event) -> Option<usize> {
+    ARCHETYPE_KEYS.iter().position(|key| is_key_down(event, *key))
+}
+
+/// The still sprite for a tower archetype, None if the archetype doesn't exist
+fn archetype_sprite(world: &World, archetype: usize) -> Option<SpriteRender> {
+    let tower_config = world.read_resource::<TowerConfig>();
+    let archetype = tower_config.archetypes.get(archetype)?;
+    let sprites = world.read_resource::<Sprites>();
+    let components = sprites
+        .get_animated_sprite_components(&archetype.sprite)
+        .unwrap_or_else(|| sprites.get_default_character_components());
+    Some(components.sprite_render)
+}
+
+/// Clicking on a built tower selects it, anywhere else tries to build one
+fn select_or_place(world: &mut World) {
+    let cell = world.read_resource::<TowerPlacement>().cell;
+    let occupied = cell.map_or(false, |cell| {
+        let maps = world.read_storage::<Map>();
+        (&maps).join().next().map_or(false, |map| map.is_occupied(cell))
+    });
+
+    if occupied {
+        world.write_resource::<Selection>().cell = cell;
+        log::info!("Selected tower at {:?}", cell.unwrap());
+    } else {
+        world.write_resource::<Selection>().cell = None;
+        place_tower(world);
+    }
+}
+
+fn sell_selected(world: &mut World) {
+    if let Some(cell) = world.write_resource::<Selection>().cell.take() {
+        world.write_resource::<Commands>().push(Command::SellTower { cell });
+    }
+}
+
+fn upgrade_selected(world: &mut World) {
+    if let Some(cell) = world.read_resource::<Selection>().cell {
+        world.write_resource::<Commands>().push(Command::UpgradeTower { cell });
+    }
+}
+
+fn cycle_selected_targeting(world: &mut World) {
+    let cell = match world.read_resource::<Selection>().cell {
+        Some(cell) => cell,
+        None => return,
+    };
+
+    let mode = {
+        let towers = world.read_storage::<Tower>();
+        let tower = (&towers).join().find(|t| t.cell == cell);
+        match tower {
+            Some(tower) => tower.targeting.next(),
+            None => return,
+        }
+    };
+    world.write_resource::<Commands>().push(Command::SetTargeting { cell, mode });
+}
+
+fn save_game(world: &mut World) {
+    let path = quicksave_path();
+    match SaveGame::capture(world).write(&path) {
+        Ok(()) => log::info!("Saved {:?}", path),
+        Err(e) => log::warn!("Failed to save {:?}: {}", path, e),
+    }
+}
+
+fn load_game(world: &mut World) {
+    let path = quicksave_path();
+    match SaveGame::read(&path) {
+        Ok(save) => {
+            save.restore(world);
+            world.write_resource::<Selection>().cell = None;
+            log::info!("Loaded {:?}", path);
+        },
+        Err(e) => log::warn!("Failed to load {:?}: {}", path, e),
+    }
+}
+
+fn place_tower(world: &mut World) {
+    let (cell, archetype, error) = {
+        let placement = world.read_resource::<TowerPlacement>();
+        (placement.cell, placement.archetype, placement.error)
+    };
+
+    if let Some(cell) = cell {
+        if let Some(error) = error {
+            log::info!("Can't build at {:?}: {}", cell, error);
+            return;
+        }
+
+        let archetype = match world.read_resource::<TowerConfig>().archetypes.get(archetype) {
+            Some(archetype) => archetype.name.clone(),
+            None => return,
+        };
+        world.write_resource::<Commands>().push(Command::PlaceTower {
+            cell,
+            archetype,
+        });
+    }
+}
+
+fn init_ghost(world: &mut World) -> Option<Entity> {
+    let sprite = archetype_sprite(world, 0)?;
+    Some(world
+        .create_entity()
+        .with(sprite)
+        .with(Transform::default())
+        .with(Transparent)
+        .with(Tint(Srgba::new(1., 1., 1., 0.)))
+        .build())
+}
+
+fn init_camera(world: &mut World) -> Entity {
     let (width, height) = {
         let dimensions = world.read_resource::<ScreenDimensions>();
         (dimensions.width(), dimensions.height())
@@ -94,5 +447,5 @@ fn init_camera(world: &mut World) {
         .create_entity()
         .with(Camera::standard_2d(width, height))
         .with(transform)
-        .build();
+        .build()
 }
\ No newline at end of file
@@ -1,26 +1,24 @@
-use amethyst::{
-    core::timing::Time,
-    ecs::prelude::{
-        Read, 
-        System, 
-        WriteStorage,
-        Join,
-    },
+use amethyst::ecs::prelude::{
+    Read, 
+    System, 
+    WriteStorage,
+    Join,
 };
-use crate::components::{
-    Age as AgeComponent,
+use crate::{
+    components::Age as AgeComponent,
+    resources::SimClock,
 };
 
 pub struct Age;
 
 impl<'s> System<'s> for Age {
     type SystemData = (
-        Read<'s, Time>,
+        Read<'s, SimClock>,
         WriteStorage<'s, AgeComponent>,
     );
 
-    fn run(&mut self, (time, mut ages): Self::SystemData) {
-        let delta_seconds = time.delta_seconds();
+    fn run(&mut self, (clock, mut ages): Self::SystemData) {
+        let delta_seconds = clock.tick_seconds;
         for a in (&mut ages).join() {
             a.age += delta_seconds;
         }
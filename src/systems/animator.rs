@@ -44,8 +44,8 @@ impl<'s> System<'s> for Animator {
                 .expect("Failed to insert AnimationControlSet component");
         }
 
-        let mut died = Vec::new();
-        for (e, animation_set, control_set, a) in (&entities, &animation_sets, &mut animation_control_sets, &mut animation).join() {
+        // Death animations stay on the entity until Murder deletes it
+        for (animation_set, control_set, a) in (&animation_sets, &mut animation_control_sets, &mut animation).join() {
             if a.next != a.current {
                 if let Some(current) = a.current {
                     if control_set.has_animation(current) {
@@ -55,10 +55,6 @@ impl<'s> System<'s> for Animator {
                 }
                 if let Some(next) = a.next {
                     let end_control = next.end_control();
-                    let is_terminal = next.is_terminal();
-                    if is_terminal {
-                        died.push(e);
-                    }
                     control_set.add_animation(
                         next,
                         &animation_set.get(&next).unwrap(),
@@ -76,9 +72,5 @@ impl<'s> System<'s> for Animator {
                 a.is_done = true;
             }
         }
-        for e in died {
-            animation.remove(e);
-        }
-
     }    
 }
\ No newline at end of file
@@ -4,6 +4,7 @@ use crate::systems::{
     Animator,
     NavigatorMover,
     ProjectileMover,
+    ProjectileHit,
     DebugDraw,
     ClearDebug,
     Bouncer,
@@ -16,6 +17,12 @@ use crate::systems::{
     PathFinder,
     TowerAim,
     TowerShoot,
+    FlowFieldBuilder,
+    Leak,
+    CommandProcessor,
+    CreepIndexer,
+    VisibilityBuilder,
+    HudDraw,
 };
 use amethyst::{
     core::{
@@ -28,6 +35,7 @@ use amethyst::{
     error::Error,
 };
 
+/// Systems that run once per rendered frame
 pub struct Bundle;
 
 impl<'a, 'b> SystemBundle<'a, 'b> for Bundle {
@@ -36,22 +44,42 @@ impl<'a, 'b> SystemBundle<'a, 'b> for Bundle {
         _world: &mut World,
         builder: &mut DispatcherBuilder<'a, 'b>,
     ) -> Result<(), Error> {
-        builder.add(MapGenerator, "map_generator_system", &[]);
+        builder.add(MapGenerator::default(), "map_generator_system", &[]);
         builder.add(ClearDebug, "clear_debug_system", &[]);
-        builder.add(NavigatorMover, "navigator_mover_system", &[]); 
-        builder.add(ProjectileMover, "projectile_mover_system", &[]); 
         //builder.add(Bouncer, "bouncer_system", &["mover_system"]);
-        builder.add(Heading, "heading_system", &["navigator_mover_system"]);     
-        builder.add(DebugDraw, "debug_draw_system", &["navigator_mover_system", "projectile_mover_system"]);
-        builder.add(Spawner::default(), "spawner_system", &[]);
+        builder.add(Heading, "heading_system", &[]);     
+        builder.add(DebugDraw, "debug_draw_system", &[]);
+        builder.add(HudDraw, "hud_draw_system", &["clear_debug_system"]);
         builder.add(FpsLog::default(), "fps_log_system", &[]);
-        builder.add(Age, "age_system", &[]);
-        builder.add(Murder, "murder_system", &["age_system"]);
         builder.add(Animator, "animator_system", &["heading_system"]);  
         builder.add(MoveCamera, "move_camera_system", &[]);
-        builder.add(PathFinder, "path_finder_system", &[]);
-        builder.add(TowerAim, "tower_aim_system", &["navigator_mover_system"]);
+        Ok(())
+    }
+}
+
+/// Gameplay systems, dispatched once per SimClock tick by the Running state
+pub struct SimulationBundle;
+
+impl<'a, 'b> SystemBundle<'a, 'b> for SimulationBundle {
+    fn build(
+        self,
+        _world: &mut World,
+        builder: &mut DispatcherBuilder<'a, 'b>,
+    ) -> Result<(), Error> {
+        builder.add(CommandProcessor, "command_processor_system", &[]);
+        builder.add(FlowFieldBuilder, "flow_field_builder_system", &["command_processor_system"]);
+        builder.add(NavigatorMover, "navigator_mover_system", &["flow_field_builder_system"]); 
+        builder.add(ProjectileMover, "projectile_mover_system", &[]); 
+        builder.add(ProjectileHit, "projectile_hit_system", &["projectile_mover_system", "navigator_mover_system"]);
+        builder.add(Spawner::default(), "spawner_system", &["command_processor_system"]);
+        builder.add(Age, "age_system", &[]);
+        builder.add(Murder, "murder_system", &["age_system"]);
+        builder.add(PathFinder, "path_finder_system", &["command_processor_system"]);
+        builder.add(CreepIndexer, "creep_indexer_system", &["navigator_mover_system"]);
+        builder.add(VisibilityBuilder, "visibility_builder_system", &["command_processor_system"]);
+        builder.add(TowerAim, "tower_aim_system", &["creep_indexer_system", "visibility_builder_system"]);
         builder.add(TowerShoot, "tower_shoot_system", &["tower_aim_system"]);
+        builder.add(Leak, "leak_system", &["navigator_mover_system"]);
         Ok(())
     }
 }
\ No newline at end of file
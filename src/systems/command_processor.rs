@@ -0,0 +1,310 @@
+use amethyst::ecs::prelude::{
+    Join, 
+    Read, 
+    ReadStorage,
+    System, 
+    WriteStorage,
+    Write,
+    WriteExpect,
+    Entities,
+    LazyUpdate,
+    ReadExpect,
+};
+use crate::{
+    components::{
+        Dying,
+        Map,
+        Navigator,
+        Tower,
+        map::PlacementError,
+    },
+    resources::{
+        Sprites,
+        Command,
+        Commands,
+        CommandLog,
+        Playback,
+        TimedCommand,
+        Gold,
+        Stats,
+        SimClock,
+        WaveProgress,
+    },
+    util::spawn::spawn_tower,
+    config::{
+        Spawner as SpawnerConfig,
+        Tower as TowerConfig,
+        Economy as EconomyConfig,
+    },
+};
+
+/// Carries out the commands queued up by the player
+pub struct CommandProcessor;
+
+impl<'s> System<'s> for CommandProcessor {
+    type SystemData = (
+        Entities<'s>,
+        Read<'s, LazyUpdate>,
+        Option<Read<'s, Sprites>>,
+        WriteStorage<'s, Map>,
+        WriteStorage<'s, Tower>,
+        ReadStorage<'s, Navigator>,
+        ReadStorage<'s, Dying>,
+        ReadExpect<'s, TowerConfig>,
+        ReadExpect<'s, SpawnerConfig>,
+        ReadExpect<'s, EconomyConfig>,
+        WriteExpect<'s, Gold>,
+        Write<'s, Commands>,
+        Write<'s, Stats>,
+        Read<'s, SimClock>,
+        Write<'s, CommandLog>,
+        Write<'s, Playback>,
+        Write<'s, WaveProgress>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            lazy_update,
+            sprites_resource,
+            mut maps,
+            mut towers,
+            navigators,
+            dying,
+            tower_config,
+            spawner_config,
+            economy_config,
+            mut gold,
+            mut commands,
+            mut stats,
+            clock,
+            mut command_log,
+            mut playback,
+            mut wave_progress,
+        ) = data;
+
+        let mut map = None;
+        for (e, m) in (&entities, &mut maps).join() {
+            if entities.is_alive(e) {
+                map = Some(m);
+                break;
+            }
+        }
+
+        let map = match map {
+            Some(map) => map,
+            None => return,
+        };
+
+        // Towers created this frame aren't in the storage yet so keep count here
+        let mut tower_count = (&entities, &towers).join().count();
+
+        if playback.is_playing() {
+            commands.pending.clear();
+            while playback.commands.front().map_or(false, |c| c.tick <= clock.tick) {
+                let timed = playback.commands.pop_front().unwrap();
+                commands.push(timed.command);
+            }
+        }
+
+        for command in commands.pending.drain(..) {
+            log::debug!("Processing {:?}", command);
+            command_log.commands.push(TimedCommand {
+                tick: clock.tick,
+                command: command.clone(),
+            });
+            match command {
+                Command::PlaceTower { cell, archetype } => {
+                    let archetype = match tower_config.archetype(&archetype) {
+                        Some(archetype) => archetype,
+                        None => {
+                            log::warn!("Can't place tower with unknown archetype {}", archetype);
+                            continue;
+                        },
+                    };
+
+                    let placement = if tower_count >= spawner_config.max_towers.unwrap_or(usize::max_value()) {
+                        Err(PlacementError::TowerLimit)
+                    } else if gold.gold < archetype.cost {
+                        Err(PlacementError::CantAfford)
+                    } else {
+                        // A creep left standing on a tower could never leave, so wait for it to move on
+                        map.can_place_tower(cell).and_then(|_| {
+                            if (&navigators, !&dying).join().any(|(n, _)| n.is_in(cell)) {
+                                Err(PlacementError::CreepInTheWay)
+                            } else {
+                                Ok(())
+                            }
+                        })
+                    };
+
+                    match placement {
+                        Ok(_) => {
+                            map.set_occupied(cell, true);
+                            spawn_tower(&entities, &lazy_update, sprites_resource.as_ref().map(|s| &**s), cell, archetype);
+                            gold.gold -= archetype.cost;
+                            stats.gold_spent += archetype.cost;
+                            stats.towers_built += 1;
+                            tower_count += 1;
+                            log::info!("Built {} tower at {:?} for {} gold, {} left", archetype.name, cell, archetype.cost, gold.gold);
+                        },
+                        Err(e) => log::warn!("Can't place {} tower at {:?}: {}", archetype.name, cell, e),
+                    }
+                },
+                Command::SellTower { cell } => {
+                    let tower = (&entities, &towers).join().find(|(e, t)| entities.is_alive(*e) && t.cell == cell);
+                    if let Some((tower_entity, tower)) = tower {
+                        let refund = (tower.value as f32 * economy_config.sell_refund).max(0.).round() as u32;
+                        entities.delete(tower_entity).expect("Failed to delete entity");
+                        map.set_occupied(cell, false);
+                        gold.gold += refund;
+                        stats.gold_earned += refund;
+                        stats.towers_sold += 1;
+                        tower_count -= 1;
+                        log::info!("Sold {} tower at {:?} for {} gold, {} left", tower.archetype, cell, refund, gold.gold);
+                    } else {
+                        log::warn!("Can't sell tower at {:?}, there isn't one", cell);
+                    }
+                },
+                Command::UpgradeTower { cell } => {
+                    let tower = (&entities, &mut towers).join().find(|(e, t)| entities.is_alive(*e) && t.cell == cell);
+                    let (tower_entity, tower) = match tower {
+                        Some(tower) => tower,
+                        None => {
+                            log::warn!("Can't upgrade tower at {:?}, there isn't one", cell);
+                            continue;
+                        },
+                    };
+
+                    let archetype = match tower_config.archetype(&tower.archetype) {
+                        Some(archetype) => archetype,
+                        None => {
+                            log::warn!("Can't upgrade tower with unknown archetype {}", tower.archetype);
+                            continue;
+                        },
+                    };
+
+                    let cost = match archetype.next_upgrade(tower.level) {
+                        Some(upgrade) => upgrade.cost,
+                        None => {
+                            log::info!("{} tower at {:?} is already at max level", archetype.name, cell);
+                            continue;
+                        },
+                    };
+
+                    if gold.gold < cost {
+                        log::info!("Can't upgrade {} tower at {:?}: {}", archetype.name, cell, PlacementError::CantAfford);
+                        continue;
+                    }
+
+                    let sprite_before = archetype.sprite(tower.level);
+                    tower.upgrade(archetype);
+                    gold.gold -= cost;
+                    stats.gold_spent += cost;
+                    stats.tower_upgrades += 1;
+
+                    let sprite = archetype.sprite(tower.level);
+                    if let (true, Some(sprites_resource)) = (sprite != sprite_before, sprites_resource.as_ref()) {
+                        match sprites_resource.get_animated_sprite_components(sprite) {
+                            Some(components) => components.insert(tower_entity, &lazy_update),
+                            None => log::warn!("Tower archetype {} has unknown sprite {}", archetype.name, sprite),
+                        }
+                    }
+                    log::info!("Upgraded {} tower at {:?} to level {} for {} gold, {} left", archetype.name, cell, tower.level, cost, gold.gold);
+                },
+                Command::SetTargeting { cell, mode } => {
+                    match (&entities, &mut towers).join().find(|(e, t)| entities.is_alive(*e) && t.cell == cell) {
+                        Some((_, tower)) => {
+                            tower.targeting = mode;
+                            log::info!("{} tower at {:?} now targets {:?}", tower.archetype, cell, mode);
+                        },
+                        None => log::warn!("Can't set targeting of tower at {:?}, there isn't one", cell),
+                    }
+                },
+                Command::StartWave => {
+                    if wave_progress.spawning {
+                        log::info!("Can't start the next wave while one is spawning");
+                    } else {
+                        wave_progress.countdown = Some(0.);
+                    }
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use amethyst::ecs::{
+        Builder,
+        RunNow,
+        World,
+        WorldExt,
+    };
+
+    fn place_tower(world: &mut World, cell: (usize, usize)) {
+        world.write_resource::<Commands>().push(Command::PlaceTower {
+            cell,
+            archetype: "Default".to_string(),
+        });
+        CommandProcessor.run_now(world);
+    }
+
+    #[test]
+    fn towers_cant_go_on_creeps() {
+        let mut world = World::new();
+        System::setup(&mut CommandProcessor, &mut world);
+        world.insert(TowerConfig::default());
+        world.insert(SpawnerConfig::default());
+        world.insert(EconomyConfig::default());
+        world.insert(Gold::new(100));
+
+        let map = Map::from_layout(&[
+            "#######",
+            "#E   X#",
+            "#     #",
+            "#######",
+        ]);
+        let map_entity = world.create_entity().with(map).build();
+        let mut navigator = Navigator::new(3, 1);
+        navigator.next = Some((4, 2));
+        world.create_entity().with(navigator).build();
+
+        // Where the creep is and where it's heading are both off limits
+        place_tower(&mut world, (3, 1));
+        place_tower(&mut world, (4, 2));
+        assert_eq!(world.read_resource::<Stats>().towers_built, 0);
+        {
+            let maps = world.read_storage::<Map>();
+            let map = maps.get(map_entity).unwrap();
+            assert!(!map.is_occupied((3, 1)));
+            assert!(!map.is_occupied((4, 2)));
+        }
+
+        place_tower(&mut world, (2, 2));
+        assert_eq!(world.read_resource::<Stats>().towers_built, 1);
+        assert!(world.read_storage::<Map>().get(map_entity).unwrap().is_occupied((2, 2)));
+    }
+
+    #[test]
+    fn dying_creeps_dont_get_in_the_way() {
+        let mut world = World::new();
+        System::setup(&mut CommandProcessor, &mut world);
+        world.insert(TowerConfig::default());
+        world.insert(SpawnerConfig::default());
+        world.insert(EconomyConfig::default());
+        world.insert(Gold::new(100));
+
+        world.create_entity().with(Map::from_layout(&[
+            "#######",
+            "#E   X#",
+            "#     #",
+            "#######",
+        ])).build();
+        world.create_entity().with(Navigator::new(3, 1)).with(Dying::new(&SimClock::default())).build();
+
+        place_tower(&mut world, (3, 1));
+        assert_eq!(world.read_resource::<Stats>().towers_built, 1);
+    }
+}
\ No newline at end of file
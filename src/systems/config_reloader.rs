@@ -0,0 +1,213 @@
+use amethyst::{
+    config::Config,
+    core::timing::Time,
+    ecs::prelude::{
+        Entities,
+        Join,
+        Read,
+        ReadExpect,
+        ReadStorage,
+        System,
+        Write,
+        WriteExpect,
+        WriteStorage,
+    },
+};
+use std::{
+    fs,
+    path::{
+        Path,
+        PathBuf,
+    },
+    time::SystemTime,
+};
+use crate::{
+    components::{
+        Creep,
+        DamageNumber,
+        Dying,
+        Map,
+        Projectile,
+        Tower,
+    },
+    config::{
+        Game as GameConfig,
+        Map as MapConfig,
+        Spawner as SpawnerConfig,
+        DebugDraw as DebugDrawConfig,
+        Tower as TowerConfig,
+        Creep as CreepConfig,
+        Player as PlayerConfig,
+        Economy as EconomyConfig,
+        Simulation as SimulationConfig,
+        Waves as WavesConfig,
+        validate,
+    },
+    resources::{
+        Gold,
+        Lives,
+        Stats,
+        WaveProgress,
+        WaveStatus,
+    },
+    util::replay::Recording,
+};
+
+/// Seconds between checks of the config file's modified time
+const POLL_SECONDS: f32 = 1.;
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Reloads game_config.ron when it changes so balance changes don't need a restart.
+/// The map is only regenerated if the map settings changed, which starts the run over
+pub struct ConfigReloader {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    elapsed: f32,
+}
+
+impl ConfigReloader {
+    pub fn new(path: PathBuf) -> Self {
+        let modified = modified_time(&path);
+        Self {
+            path,
+            modified,
+            elapsed: 0.,
+        }
+    }
+}
+
+impl<'s> System<'s> for ConfigReloader {
+    type SystemData = (
+        Entities<'s>,
+        Read<'s, Time>,
+        WriteExpect<'s, MapConfig>,
+        WriteExpect<'s, SpawnerConfig>,
+        WriteExpect<'s, DebugDrawConfig>,
+        WriteExpect<'s, TowerConfig>,
+        WriteExpect<'s, CreepConfig>,
+        WriteExpect<'s, PlayerConfig>,
+        WriteExpect<'s, EconomyConfig>,
+        ReadExpect<'s, SimulationConfig>,
+        ReadExpect<'s, WavesConfig>,
+        ReadStorage<'s, Map>,
+        WriteStorage<'s, Tower>,
+        ReadStorage<'s, Creep>,
+        ReadStorage<'s, Projectile>,
+        ReadStorage<'s, Dying>,
+        ReadStorage<'s, DamageNumber>,
+        Option<Read<'s, Recording>>,
+        WriteExpect<'s, Gold>,
+        WriteExpect<'s, Lives>,
+        Write<'s, WaveStatus>,
+        Write<'s, WaveProgress>,
+        Write<'s, Stats>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            time,
+            mut map_config,
+            mut spawner_config,
+            mut debug_draw_config,
+            mut tower_config,
+            mut creep_config,
+            mut player_config,
+            mut economy_config,
+            simulation_config,
+            waves_config,
+            maps,
+            mut towers,
+            creeps,
+            projectiles,
+            dying,
+            damage_numbers,
+            recording,
+            mut gold,
+            mut lives,
+            mut wave_status,
+            mut wave_progress,
+            mut stats,
+        ) = data;
+
+        // Real time so it still polls while the game is paused
+        self.elapsed += time.delta_real_seconds();
+        if self.elapsed < POLL_SECONDS {
+            return;
+        }
+        self.elapsed = 0.;
+
+        let modified = modified_time(&self.path);
+        if modified == self.modified {
+            return;
+        }
+        self.modified = modified;
+
+        let config = match GameConfig::load_no_fallback(&self.path) {
+            Ok(config) => config,
+            Err(e) => {
+                log::error!("Failed to reload {:?}, keeping the current config: {}", self.path, e);
+                return;
+            },
+        };
+        let problems = validate::validate(&config, &waves_config);
+        if !problems.is_empty() {
+            log::error!("Not reloading {:?}, keeping the current config. {}", self.path, validate::report(&problems));
+            return;
+        }
+        log::info!("Reloaded {:?}", self.path);
+
+        if recording.is_some() {
+            log::warn!("The replay being recorded won't play back the same after a config reload");
+        }
+        if config.simulation.tick_rate != simulation_config.tick_rate {
+            log::warn!("simulation.tick_rate only changes on a restart");
+        }
+
+        let regenerate = config.map != *map_config;
+
+        *map_config = config.map;
+        *spawner_config = config.spawner;
+        *debug_draw_config = config.debug_draw;
+        *tower_config = config.tower;
+        *creep_config = config.creep;
+        *player_config = config.player;
+        *economy_config = config.economy;
+
+        // Built towers keep their level but take the archetype's new stats
+        for tower in (&mut towers).join() {
+            if let Some(archetype) = tower_config.archetype(&tower.archetype) {
+                let stats = archetype.stats(tower.level);
+                tower.range = stats.range;
+                tower.frequency = stats.frequency;
+            }
+        }
+
+        if regenerate {
+            log::info!("Map settings changed, regenerating the map");
+            // Everything on the old map goes with it, MapGenerator makes the new one
+            let old = (&entities, &maps).join().map(|(e, _)| e)
+                .chain((&entities, &towers).join().map(|(e, _)| e))
+                .chain((&entities, &creeps).join().map(|(e, _)| e))
+                .chain((&entities, &projectiles).join().map(|(e, _)| e))
+                .chain((&entities, &dying).join().map(|(e, _)| e))
+                .chain((&entities, &damage_numbers).join().map(|(e, _)| e));
+            for e in old {
+                entities.delete(e).expect("Failed to delete entity");
+            }
+
+            // The towers were paid for on the old map, so the run starts over from the new config
+            *gold = Gold::new(economy_config.starting_gold);
+            *lives = Lives::new(player_config.lives);
+            *wave_progress = WaveProgress::default();
+            *wave_status = WaveStatus {
+                wave_count: waves_config.waves.len(),
+                ..Default::default()
+            };
+            *stats = Stats::default();
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,38 @@
+use amethyst::{
+    core::transform::Transform,
+    ecs::prelude::{
+        Join, 
+        ReadStorage, 
+        System, 
+        Write,
+        Entities,
+    },
+};
+use crate::{
+    components::Creep,
+    resources::CreepGrid,
+    util::screen_to_iso,
+};
+
+/// Rebuilds the CreepGrid from this frame's creep positions
+pub struct CreepIndexer;
+
+impl<'s> System<'s> for CreepIndexer {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, Creep>,
+        ReadStorage<'s, Transform>,
+        Write<'s, CreepGrid>,
+    );
+
+    fn run(&mut self, (entities, creeps, transforms, mut grid): Self::SystemData) {
+        grid.clear();
+
+        for (creep_entity, _, transform) in (&entities, &creeps, &transforms).join() {
+            // Skip any dead ones
+            if !entities.is_alive(creep_entity) { continue }
+
+            grid.insert(creep_entity, screen_to_iso(transform.translation().xy()));
+        }
+    }
+}
\ No newline at end of file
@@ -17,6 +17,7 @@ use amethyst::{
         System, 
         WriteStorage, 
         Entities,
+        Read,
         ReadExpect,
     },
 };
@@ -30,9 +31,9 @@ use crate::{
         Path,
         Color,
         Tower,
-        Map,
         Projectile,
     },
+    resources::Visibility,
     util::{
         iso_to_screen,
         TILE_W,
@@ -54,8 +55,8 @@ impl<'s> System<'s> for DebugDraw {
         WriteStorage<'s, DebugLinesComponent>,
         WriteStorage<'s, Color>,
         ReadExpect<'s, DebugDrawConfig>,
-        ReadStorage<'s, Map>,
         ReadStorage<'s, Projectile>,
+        Read<'s, Visibility>,
     );
 
     fn run(&mut self, (
@@ -67,8 +68,8 @@ impl<'s> System<'s> for DebugDraw {
         mut debug_comps,
         mut colors,
         config,
-        maps,
         projectiles,
+        visibility,
     ): Self::SystemData) {
         #[cfg(feature = "profiler")]
         profile_scope!("debug_draw_system");
@@ -127,7 +128,7 @@ impl<'s> System<'s> for DebugDraw {
                 }
             }
 
-            if config.tower_range || config.tower_target {
+            if config.tower_range || config.tower_target || config.tower_los {
                 if let Some(tower) = towers.get(entity) {
                     if config.tower_range {
                         add_ellipse_2d(
@@ -140,38 +141,25 @@ impl<'s> System<'s> for DebugDraw {
                         );
                     }
 
-                    let mut map = None;
-
                     if config.tower_los {
-                        for (e, m) in (&entities, &maps).join() {
-                            if !entities.is_alive(e) { continue }
-                            map = Some(m);
-                            break;
+                        if let Some(visible) = visibility.get(tower.cell) {
+                            for cell in &visible.cells {
+                                let pos = iso_to_screen(Vector2::new(cell.0 as f32, cell.1 as f32));
+                                let screen = Point3::new(pos.x, pos.y, DEBUG_Z);
+
+                                debug.add_circle_2d(
+                                    screen,
+                                    4.,
+                                    4,
+                                    color,
+                                );
+                            }
                         }
                     }
 
                     if config.tower_target {
                         if let Some(target) = tower.target {
                             if let Some(target_transform) = transforms.get(target) {
-                                if let Some(map) = map {
-                                    if let (Some(origin), Some(target)) = (
-                                        map.world_to_cell_index(transform.translation().xy()),
-                                        map.world_to_cell_index(target_transform.translation().xy())
-                                    ) {
-                                        for visit in map.ray_visit(origin, target) {
-                                            let pos = iso_to_screen(Vector2::new(visit.0 as f32, visit.1 as f32));
-                                            let screen = Point3::new(pos.x, pos.y, DEBUG_Z);
-                                            
-                                            debug.add_circle_2d(
-                                                screen,
-                                                10.,
-                                                20,
-                                                color,
-                                            );                                            
-                                        }
-                                    }
-                                }                                
-
                                 let mut target = Point3::from(*target_transform.translation());
                                 target.z = DEBUG_Z;
 
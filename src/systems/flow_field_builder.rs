@@ -0,0 +1,45 @@
+use amethyst::ecs::prelude::{
+    Join, 
+    ReadStorage, 
+    System, 
+    Write,
+    Entities,
+    ReadExpect,
+};
+use crate::{
+    components::Map,
+    resources::FlowFields,
+    config::{
+        Map as MapConfig,
+        map::Navigation,
+    },
+};
+
+/// Rebuilds the flow fields whenever the map changes
+pub struct FlowFieldBuilder;
+
+impl<'s> System<'s> for FlowFieldBuilder {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, Map>,
+        Write<'s, FlowFields>,
+        ReadExpect<'s, MapConfig>,
+    );
+
+    fn run(&mut self, (entities, maps, mut flow_fields, map_config): Self::SystemData) {
+        if map_config.navigation != Navigation::FlowField {
+            return;
+        }
+
+        for (map_entity, map) in (&entities, &maps).join() {
+            if !entities.is_alive(map_entity) { continue }
+
+            if !flow_fields.is_current(map_entity, map) {
+                let goals: Vec<_> = map.exit().into_iter().collect();
+                flow_fields.rebuild(map_entity, map, &goals);
+                log::info!("Rebuilt flow fields for {:?} version {}", map_entity, map.version());
+            }
+            break;
+        }
+    }
+}
\ No newline at end of file
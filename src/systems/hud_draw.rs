@@ -0,0 +1,136 @@
+use amethyst::{
+    renderer::{
+        debug_drawing::DebugLinesComponent,
+        palette::Srgba,
+    },
+    core::{
+        transform::Transform,
+        math::{
+            Vector3,
+            Point3,
+        },
+    },
+    ecs::prelude::{
+        Join,
+        ReadStorage, 
+        System, 
+        WriteStorage, 
+        Entities,
+    },
+};
+use crate::{
+    components::{
+        Age,
+        Creep,
+        DamageNumber,
+        Health,
+    },
+    util::{
+        TILE_W,
+        TILE_H,
+        constants::HUD_Z,
+    },
+};
+
+const HEALTH_BAR_WIDTH: f32 = TILE_W * 0.75;
+/// Lines stacked up to give the bar some thickness
+const HEALTH_BAR_LINES: usize = 3;
+/// Height of the bar above the creep's origin
+const HEALTH_BAR_OFFSET: f32 = TILE_H * 2.5;
+
+const DIGIT_W: f32 = 4.;
+const DIGIT_H: f32 = 8.;
+const DIGIT_SPACING: f32 = 3.;
+/// How far a damage number rises over its life
+const DAMAGE_NUMBER_RISE: f32 = TILE_H * 2.;
+
+/// Seven segment display segments a to g (top, top right, bottom right, bottom,
+/// bottom left, top left, middle) for each digit, a in the lowest bit
+const DIGIT_SEGMENTS: [u8; 10] = [0x3F, 0x06, 0x5B, 0x4F, 0x66, 0x6D, 0x7D, 0x07, 0x7F, 0x6F];
+
+/// The ends of each segment within a digit, from the bottom left corner
+const SEGMENT_LINES: [((f32, f32), (f32, f32)); 7] = [
+    ((0., DIGIT_H), (DIGIT_W, DIGIT_H)),
+    ((DIGIT_W, DIGIT_H / 2.), (DIGIT_W, DIGIT_H)),
+    ((DIGIT_W, 0.), (DIGIT_W, DIGIT_H / 2.)),
+    ((0., 0.), (DIGIT_W, 0.)),
+    ((0., 0.), (0., DIGIT_H / 2.)),
+    ((0., DIGIT_H / 2.), (0., DIGIT_H)),
+    ((0., DIGIT_H / 2.), (DIGIT_W, DIGIT_H / 2.)),
+];
+
+/// Draws value with the middle of its bottom edge at centre, using lines as there is no text rendering
+fn draw_number(debug: &mut DebugLinesComponent, centre: Point3<f32>, value: u32, color: Srgba) {
+    let digits = value.to_string();
+    let width = digits.len() as f32 * (DIGIT_W + DIGIT_SPACING) - DIGIT_SPACING;
+    let mut left = centre.x - width / 2.;
+
+    for digit in digits.chars().filter_map(|c| c.to_digit(10)) {
+        let segments = DIGIT_SEGMENTS[digit as usize];
+        for (i, ((x0, y0), (x1, y1))) in SEGMENT_LINES.iter().enumerate() {
+            if segments & (1 << i) == 0 { continue }
+            debug.add_line(
+                Point3::new(left + x0, centre.y + y0, centre.z),
+                Point3::new(left + x1, centre.y + y1, centre.z),
+                color,
+            );
+        }
+        left += DIGIT_W + DIGIT_SPACING;
+    }
+}
+
+/// Draws health bars over damaged creeps and the floating damage numbers
+pub struct HudDraw;
+
+impl<'s> System<'s> for HudDraw {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, Creep>,
+        ReadStorage<'s, Health>,
+        ReadStorage<'s, DamageNumber>,
+        ReadStorage<'s, Age>,
+        WriteStorage<'s, DebugLinesComponent>,
+    );
+
+    fn run(&mut self, (entities, transforms, creeps, healths, damage_numbers, ages, mut debug_comps): Self::SystemData) {
+        for (entity, transform, creep, health, debug) in (&entities, &transforms, &creeps, &healths, &mut debug_comps).join() {
+            if !entities.is_alive(entity) || health.health >= health.max_health { continue }
+
+            let fraction = (health.health / health.max_health).max(0.).min(1.);
+            let origin = transform.translation() + Vector3::new(-HEALTH_BAR_WIDTH / 2., HEALTH_BAR_OFFSET * creep.size, 0.);
+            let split = origin.x + HEALTH_BAR_WIDTH * fraction;
+            let right = origin.x + HEALTH_BAR_WIDTH;
+
+            for i in 0..HEALTH_BAR_LINES {
+                let y = origin.y + i as f32;
+                if fraction > 0. {
+                    debug.add_line(
+                        Point3::new(origin.x, y, HUD_Z),
+                        Point3::new(split, y, HUD_Z),
+                        Srgba::new(0.2, 0.9, 0.2, 1.),
+                    );
+                }
+                if fraction < 1. {
+                    debug.add_line(
+                        Point3::new(split, y, HUD_Z),
+                        Point3::new(right, y, HUD_Z),
+                        Srgba::new(0.8, 0.1, 0.1, 1.),
+                    );
+                }
+            }
+        }
+
+        for (entity, transform, damage_number, age, debug) in (&entities, &transforms, &damage_numbers, &ages, &mut debug_comps).join() {
+            if !entities.is_alive(entity) { continue }
+
+            let life = age.max_age.map_or(0., |max_age| (age.age / max_age).max(0.).min(1.));
+            let mut centre = Point3::from(*transform.translation());
+            centre.y += HEALTH_BAR_OFFSET + DAMAGE_NUMBER_RISE * life;
+            centre.z = HUD_Z;
+
+            let damage = damage_number.damage.round() as u32;
+            draw_number(debug, centre, damage, Srgba::new(1., 0.9, 0.3, 1. - life));
+        }
+    }
+}
\ No newline at end of file
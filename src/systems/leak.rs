@@ -0,0 +1,61 @@
+use amethyst::ecs::prelude::{
+    Join, 
+    ReadStorage, 
+    System, 
+    Write,
+    WriteExpect,
+    Entities,
+};
+use crate::{
+    components::{
+        Creep,
+        Navigator,
+        Path,
+        Map,
+    },
+    resources::{
+        Lives,
+        Stats,
+    },
+};
+
+/// Removes creeps that reach the exit and takes a life for each
+pub struct Leak;
+
+impl<'s> System<'s> for Leak {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, Creep>,
+        ReadStorage<'s, Navigator>,
+        ReadStorage<'s, Path>,
+        ReadStorage<'s, Map>,
+        WriteExpect<'s, Lives>,
+        Write<'s, Stats>,
+    );
+
+    fn run(&mut self, (entities, creeps, navigators, paths, maps, mut lives, mut stats): Self::SystemData) {
+        let mut exit = None;
+        for (e, m) in (&entities, &maps).join() {
+            if !entities.is_alive(e) { continue }
+            exit = m.exit();
+            break;
+        }
+
+        let exit = match exit {
+            Some(exit) => exit,
+            None => return,
+        };
+
+        for (e, _, n, p) in (&entities, &creeps, &navigators, (&paths).maybe()).join() {
+            if !entities.is_alive(e) { continue }
+
+            let arrived = p.map(|p| p.is_complete()).unwrap_or(false) || (n.x, n.y) == exit;
+            if arrived {
+                entities.delete(e).expect("Failed to delete entity");
+                lives.lives = lives.lives.saturating_sub(1);
+                stats.leaks += 1;
+                log::info!("Creep {:?} reached the exit, {} lives left", e, lives.lives);
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -11,6 +11,8 @@ use amethyst::{
         LazyUpdate,
         Read,
         Builder,
+        Entity,
+        Join,
     },
     renderer::Transparent,
 };
@@ -18,6 +20,7 @@ use crate::{
     components::Map,
     config::Map as MapConfig,
     resources::{ 
+        SimRng,
         Sprites,
         TileDirection,
     },
@@ -30,7 +33,13 @@ use crate::{
     }
 };
 
-pub struct MapGenerator;
+/// Generates a map when there isn't one and creates the tiles for whichever map is current
+#[derive(Default)]
+pub struct MapGenerator {
+    /// The map entity the tiles were created for
+    built_for: Option<Entity>,
+    tiles: Vec<Entity>,
+}
 
 fn tile_map(neigh: u32) -> u32 {
     match neigh {
@@ -90,119 +99,143 @@ impl<'s> System<'s> for MapGenerator {
         Entities<'s>,
         ReadStorage<'s, Map>,
         ReadExpect<'s, MapConfig>,
+        ReadExpect<'s, SimRng>,
         Option<Read<'s, Sprites>>,
         Read<'s, LazyUpdate>,
     );
 
-    fn run(&mut self, (entities, maps, map_config, sprites, lazy_update): Self::SystemData) {
-        if maps.count() == 0 && 
-            sprites.is_some() //The sprites resource is created in the loading state but this system gets called immediatly
-        {
-            let w = map_config.width;
-            let h = map_config.height;
-            let mut map = Map::new(w, h);
-            let w = w as i32;
-            let h = h as i32;
-            map.generate(&map_config);
+    fn run(&mut self, (entities, maps, map_config, rng, sprites, lazy_update): Self::SystemData) {
+        //The sprites resource is created in the loading state but this system gets called immediatly
+        let sprites = match sprites {
+            Some(sprites) => sprites,
+            None => return,
+        };
 
-            let is_wall = |x, y| {
-                x < 0 || x >= w || y < 0 || y >= h || map.is_wall(x as u32, y as u32)
-            };
-        
-            for x in (0..w).rev() {
-                for y in (0..h).rev() {
-                    let this = is_wall(x, y);
-                    
-                    let wall = {
-                        if !this {
-                            None
-                        } else {
-                            let south = is_wall(x - 1, y);
-                            let north = is_wall(x + 1, y);
-                            let west = is_wall(x, y - 1);
-                            let east = is_wall(x, y + 1);
-                            let north_east = north && east && is_wall(x + 1, y + 1);
-                            let north_west = north && west && is_wall(x + 1, y - 1);
-                            let south_east = south && east && is_wall(x - 1, y + 1);
-                            let south_west = south && west && is_wall(x - 1, y - 1);
+        let current = (&entities, &maps).join().find(|(e, _)| entities.is_alive(*e));
+        if current.map(|(e, _)| e) != self.built_for {
+            // The map was replaced by loading a save or removed by a config reload
+            for tile in self.tiles.drain(..) {
+                entities.delete(tile).expect("Failed to delete entity");
+            }
+        }
 
-                            let neighbour_index = 
-                                1  * (north_west as i32) +  2  * (north as i32) +     4   * (north_east as i32) +
-                                8  * (west as i32) +                                  16  * (east as i32) +
-                                32 * (south_west as i32) +  64 * (south as i32) +     128 * (south_east as i32);
+        match current {
+            None => {
+                let mut map = Map::new(map_config.width, map_config.height);
+                map.generate_with_seed(&map_config, rng.map_seed(&map_config));
+                self.create_tiles(&entities, &lazy_update, &sprites, &map);
 
-                            let tile = tile_map(neighbour_index as u32);    
+                let map_entity = lazy_update.create_entity(&entities)
+                    .with(map)
+                    .build();
+                self.built_for = Some(map_entity);
+            },
+            Some((map_entity, map)) if self.built_for != Some(map_entity) => {
+                self.create_tiles(&entities, &lazy_update, &sprites, map);
+                self.built_for = Some(map_entity);
+            },
+            Some(_) => {},
+        }
+    }
+}
 
-                            Some(match tile {
-                                36 => TileDirection::West,
-                                41 => TileDirection::InnerCornerNorthWest,
-                                12 => TileDirection::North,
-                                28 => TileDirection::East,
-                                33 => TileDirection::InnerCornerNorthEast,
-                                42 => TileDirection::South,
-                                45 => TileDirection::InnerCornerSouthWest,
-                                44 => TileDirection::InnerCornerSouthEast,
-                                46 => TileDirection::Solid,
-                                7 => TileDirection::OuterCornerSouthEast,
-                                4 => TileDirection::OuterCornerSouthWest,
-                                34 => TileDirection::OuterCornerNorthEast,
-                                26 => TileDirection::OuterCornerNorthWest,
-                                // The following aren't exact matches
-                                35 | 14 | 19 | 20 => TileDirection::West,
-                                8 | 11 | 10 | 9 => TileDirection::North,
-                                27 => TileDirection::East,
-                                29 => TileDirection::South,
+impl MapGenerator {
+    fn create_tiles(&mut self, entities: &Entities, lazy_update: &LazyUpdate, sprites: &Sprites, map: &Map) {
+        let w = map.width() as i32;
+        let h = map.height() as i32;
+        let is_wall = |x, y| {
+            x < 0 || x >= w || y < 0 || y >= h || map.is_wall(x as u32, y as u32)
+        };
+    
+        for x in (0..w).rev() {
+            for y in (0..h).rev() {
+                let this = is_wall(x, y);
+                
+                let wall = {
+                    if !this {
+                        None
+                    } else {
+                        let south = is_wall(x - 1, y);
+                        let north = is_wall(x + 1, y);
+                        let west = is_wall(x, y - 1);
+                        let east = is_wall(x, y + 1);
+                        let north_east = north && east && is_wall(x + 1, y + 1);
+                        let north_west = north && west && is_wall(x + 1, y - 1);
+                        let south_east = south && east && is_wall(x - 1, y + 1);
+                        let south_west = south && west && is_wall(x - 1, y - 1);
 
-                                40 | 17 | 37 | 25 | 
-                                21 | 15 | 16 | 39 |
-                                22 | 24 | 23 | 38 => TileDirection::InnerCornerNorthWest,
-                                31 => TileDirection::InnerCornerNorthEast,
-                                43 => TileDirection::InnerCornerSouthWest,
+                        let neighbour_index = 
+                            1  * (north_west as i32) +  2  * (north as i32) +     4   * (north_east as i32) +
+                            8  * (west as i32) +                                  16  * (east as i32) +
+                            32 * (south_west as i32) +  64 * (south as i32) +     128 * (south_east as i32);
 
-                                1 | 5 | 6 => TileDirection::OuterCornerSouthEast,
-                                13 | 18 => TileDirection::OuterCornerNorthEast,
-                                2 | 3 => TileDirection::OuterCornerSouthWest,
+                        let tile = tile_map(neighbour_index as u32);    
 
-                                32 | 30 => TileDirection::Solid,
-                                //This is a bad match (it's a single column)
-                                0 => TileDirection::OuterCornerSouthEast,
+                        Some(match tile {
+                            36 => TileDirection::West,
+                            41 => TileDirection::InnerCornerNorthWest,
+                            12 => TileDirection::North,
+                            28 => TileDirection::East,
+                            33 => TileDirection::InnerCornerNorthEast,
+                            42 => TileDirection::South,
+                            45 => TileDirection::InnerCornerSouthWest,
+                            44 => TileDirection::InnerCornerSouthEast,
+                            46 => TileDirection::Solid,
+                            7 => TileDirection::OuterCornerSouthEast,
+                            4 => TileDirection::OuterCornerSouthWest,
+                            34 => TileDirection::OuterCornerNorthEast,
+                            26 => TileDirection::OuterCornerNorthWest,
+                            // The following aren't exact matches
+                            35 | 14 | 19 | 20 => TileDirection::West,
+                            8 | 11 | 10 | 9 => TileDirection::North,
+                            27 => TileDirection::East,
+                            29 => TileDirection::South,
 
-                                t => {
-                                    log::info!("Blob: {}", t);
-                                    TileDirection::Blob(t as usize)
-                                },
-                            })
-                        }
-                    };
+                            40 | 17 | 37 | 25 | 
+                            21 | 15 | 16 | 39 |
+                            22 | 24 | 23 | 38 => TileDirection::InnerCornerNorthWest,
+                            31 => TileDirection::InnerCornerNorthEast,
+                            43 => TileDirection::InnerCornerSouthWest,
 
+                            1 | 5 | 6 => TileDirection::OuterCornerSouthEast,
+                            13 | 18 => TileDirection::OuterCornerNorthEast,
+                            2 | 3 => TileDirection::OuterCornerSouthWest,
 
-                    create_tile(
-                        &entities,
-                        &lazy_update,
-                        sprites.as_ref().expect("Missing sprites resource"),
-                        TileDirection::Floor,
-                        x as f32, 
-                        y as f32, 
-                        FLOOR_Z_OFFSET,
-                    );
+                            32 | 30 => TileDirection::Solid,
+                            //This is a bad match (it's a single column)
+                            0 => TileDirection::OuterCornerSouthEast,
 
-                    if let Some(wall) = wall {
-                        create_tile(
-                            &entities,
-                            &lazy_update,
-                            sprites.as_ref().expect("Missing sprites resource"),
-                            wall,
-                            x as f32, 
-                            y as f32, 
-                            WALL_Z_OFFSET,
-                        );
+                            t => {
+                                log::info!("Blob: {}", t);
+                                TileDirection::Blob(t as usize)
+                            },
+                        })
                     }
+                };
+
+
+                self.tiles.push(create_tile(
+                    entities,
+                    lazy_update,
+                    sprites,
+                    TileDirection::Floor,
+                    x as f32, 
+                    y as f32, 
+                    FLOOR_Z_OFFSET,
+                ));
+
+                if let Some(wall) = wall {
+                    self.tiles.push(create_tile(
+                        entities,
+                        lazy_update,
+                        sprites,
+                        wall,
+                        x as f32, 
+                        y as f32, 
+                        WALL_Z_OFFSET,
+                    ));
                 }
             }
-
-            lazy_update.create_entity(&entities)
-                .with(map)
-                .build();
         }
     }
 }
@@ -215,7 +248,7 @@ fn create_tile(
         x: f32,
         y: f32,
         z_offset: f32
-) {
+) -> Entity {
     let transform = {
         let mut screen_pos = iso_to_screen(Vector2::new(x, y));
         screen_pos.z += z_offset;
@@ -232,7 +265,7 @@ fn create_tile(
         .with(sprite)
         .with(transform)
         .with(Transparent)
-        .build();
+        .build()
 }
 
 
@@ -13,6 +13,9 @@ pub use navigator_mover::*;
 mod projectile_mover;
 pub use projectile_mover::*;
 
+mod projectile_hit;
+pub use projectile_hit::*;
+
 mod debug_draw;
 pub use debug_draw::*;
 
@@ -47,4 +50,25 @@ mod tower_aim;
 pub use tower_aim::*;
 
 mod tower_shoot;
-pub use tower_shoot::*;
\ No newline at end of file
+pub use tower_shoot::*;
+
+mod flow_field_builder;
+pub use flow_field_builder::*;
+
+mod leak;
+pub use leak::*;
+
+mod command_processor;
+pub use command_processor::*;
+
+mod creep_indexer;
+pub use creep_indexer::*;
+
+mod visibility_builder;
+pub use visibility_builder::*;
+
+mod hud_draw;
+pub use hud_draw::*;
+
+mod config_reloader;
+pub use config_reloader::*;
\ No newline at end of file
@@ -1,6 +1,5 @@
 use amethyst::{
-    core::transform::Transform,
-    ecs::prelude::{Join, ReadStorage, System, WriteStorage, Entities},
+    ecs::prelude::{Join, Read, ReadStorage, System, WriteStorage, Entities},
 };
 
 use crate::{
@@ -9,12 +8,12 @@ use crate::{
         Animation,
         Velocity,
         Creep,
+        Dying,
     },
-    resources::{
-        AnimationId,
-    },
+    resources::SimClock,
 };
 
+/// Kills entities that are too old and deletes dying ones once their time is up
 pub struct Murder;
 
 impl<'s> System<'s> for Murder {
@@ -23,55 +22,109 @@ impl<'s> System<'s> for Murder {
         ReadStorage<'s, Age>,
         WriteStorage<'s, Animation>,
         WriteStorage<'s, Velocity>,
-        WriteStorage<'s, Transform>,
         WriteStorage<'s, Creep>,
+        WriteStorage<'s, Dying>,
+        Read<'s, SimClock>,
     );
 
-    fn run(&mut self, (entities, ages, mut animation, mut velocities, mut transforms, mut creeps): Self::SystemData) {
-        for (e, age) in (&entities, &ages).join() {
-            if !entities.is_alive(e) {
-                continue;
+    fn run(&mut self, (entities, ages, mut animation, mut velocities, mut creeps, mut dying, clock): Self::SystemData) {
+        for (e, d) in (&entities, &dying).join() {
+            if entities.is_alive(e) && clock.tick >= d.end_tick {
+                entities.delete(e).expect("Failed to delete entity");
             }
-            if let Some(max_age) = age.max_age {
-                if age.age > max_age {
-                    if let Some(anim) = animation.get_mut(e) {
-                        velocities.remove(e);
-                        creeps.remove(e);
+        }
 
-                        let (next, wait) = match anim.current {
-                            Some(AnimationId::WalkUp) => (Some(AnimationId::DieUp), false),
-                            Some(AnimationId::WalkDown) => (Some(AnimationId::DieDown), false),
-                            Some(AnimationId::WalkLeft) => (Some(AnimationId::DieLeft), false),
-                            Some(AnimationId::WalkRight) => (Some(AnimationId::DieRight), false),
-                            Some(AnimationId::WalkUpLeft) => (Some(AnimationId::DieUpLeft), false),
-                            Some(AnimationId::WalkDownLeft) => (Some(AnimationId::DieDownLeft), false),
-                            Some(AnimationId::WalkUpRight) => (Some(AnimationId::DieUpRight), false),
-                            Some(AnimationId::WalkDownRight) => (Some(AnimationId::DieDownRight), false),
-                            /*
-                            Some(AnimationId::DieUp) => (None, false),
-                            Some(AnimationId::DieDown) => (None, false),
-                            Some(AnimationId::DieLeft) => (None, false),
-                            Some(AnimationId::DieRight) => (None, false),
-                            Some(AnimationId::DieUpLeft) => (None, false),
-                            Some(AnimationId::DieDownLeft) => (None, false),
-                            Some(AnimationId::DieUpRight) => (None, false),
-                            Some(AnimationId::DieDownRight) => (None, false), 
-                            */
-                            _ => (None, false),
-                        };
+        let expired: Vec<_> = (&entities, &ages, !&dying).join()
+            .filter(|(e, age, _)| entities.is_alive(*e) && age.max_age.map_or(false, |max_age| age.age > max_age))
+            .map(|(e, _, _)| e)
+            .collect();
 
-                        if (!wait || anim.is_done) && next.is_some() {
-                            anim.next = next;
-                            if wait { //Hack
-                                if let Some(transform) = transforms.get_mut(e) {
-                                    let z = transform.translation().z * 0.99;
-                                    transform.set_translation_z(z);
-                                }
-                            }
-                        }
-                    }
+        for e in expired {
+            // Every creep gets an Animation whether or not there are sprites, so this
+            // doesn't depend on rendering
+            if let Some(anim) = animation.get_mut(e) {
+                velocities.remove(e);
+                creeps.remove(e);
+                dying.insert(e, Dying::new(&clock)).expect("Failed to insert Dying component");
+
+                // Only for show, the death animation doesn't decide when it goes
+                if let Some(death) = anim.current.and_then(|a| a.death()) {
+                    anim.next = Some(death);
                 }
+            } else {
+                // Nothing to animate so it can go straight away
+                entities.delete(e).expect("Failed to delete entity");
             }
         }
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use amethyst::ecs::{
+        Builder,
+        Entity,
+        RunNow,
+        World,
+        WorldExt,
+    };
+    use crate::{
+        components::DEATH_SECONDS,
+        resources::AnimationId,
+    };
+
+    fn expired_creep(world: &mut World, animation: Animation) -> Entity {
+        world.create_entity()
+            .with(Age {
+                age: 2.,
+                max_age: Some(1.),
+            })
+            .with(animation)
+            .build()
+    }
+
+    #[test]
+    fn dying_takes_the_same_ticks_however_far_the_animation_got() {
+        let mut world = World::new();
+        System::setup(&mut Murder, &mut world);
+
+        let unanimated = expired_creep(&mut world, Animation::default());
+        // As if a fast renderer had already played the whole death animation
+        let played_out = expired_creep(&mut world, Animation {
+            current: Some(AnimationId::DieDown),
+            next: Some(AnimationId::DieDown),
+            is_done: true,
+        });
+
+        let mut ticks = 0u64;
+        while world.is_alive(unanimated) || world.is_alive(played_out) {
+            assert_eq!(world.is_alive(unanimated), world.is_alive(played_out), "deleted on different ticks");
+            assert!(ticks < 100, "never deleted");
+            world.write_resource::<SimClock>().advance();
+            Murder.run_now(&world);
+            world.maintain();
+            ticks += 1;
+        }
+
+        // One tick to start dying and then the full death time
+        let tick_seconds = world.read_resource::<SimClock>().tick_seconds;
+        assert_eq!(ticks, 1 + (DEATH_SECONDS / tick_seconds).ceil() as u64);
+    }
+
+    #[test]
+    fn expired_entities_without_animations_go_straight_away() {
+        let mut world = World::new();
+        System::setup(&mut Murder, &mut world);
+        let number = world.create_entity()
+            .with(Age {
+                age: 2.,
+                max_age: Some(1.),
+            })
+            .build();
+
+        Murder.run_now(&world);
+        world.maintain();
+        assert!(!world.is_alive(number));
+    }
 }
\ No newline at end of file
@@ -1,25 +1,33 @@
 use amethyst::{
     core::{
-        timing::Time, 
         transform::Transform,
         math::Vector2,
     },
     ecs::prelude::{
         Join, 
         Read, 
+        ReadStorage,
+        ReadExpect,
         System, 
         WriteStorage,
+        Entities,
     },
 };
-use std::time::{
-    Instant,
-    Duration,
-};
 use crate::{
     components::{
         Velocity,
         Path,
         Navigator,
+        Map,
+    },
+    resources::{
+        FlowField,
+        FlowFields,
+        SimClock,
+    },
+    config::{
+        Map as MapConfig,
+        map::Navigation,
     },
     util::{
         constants::CHARACTER_Z_OFFSET,
@@ -30,63 +38,133 @@ use crate::{
 
 pub struct NavigatorMover;
 
+/// Moves along an A* path, returns the new map position or None once the end is reached
+fn follow_path(v: &mut Velocity, p: &mut Path, n: &mut Navigator, now: f32) -> Option<Vector2<f32>> {
+    let (path, i) = match (&p.path, p.path_i) {
+        (Some((path, _)), Some(i)) => (path, i),
+        _ => return None,
+    };
+    if i >= path.len() { 
+        return None;
+    }
+    let next_pos: Vector2<f32> = path[i].into();
+    n.next = Some((path[i].x as usize, path[i].y as usize));
+    let prev_pos: Vector2<f32> = if i == 0 {
+        n.clone().into()
+    } else {
+        path[i - 1].into()
+    };
+
+    // Update the velocity angle
+    v.velocity = next_pos - prev_pos;
+
+    // Scale it to the right speed
+    set_magnitude(&mut v.velocity, v.speed);
+
+    let distance = prev_pos.metric_distance(&next_pos);
+    let travel_time = distance / v.speed;
+    let start_time = p.start_time.unwrap_or(now);
+    let elapsed_time = now - start_time;
+
+    if elapsed_time >= travel_time {
+        p.path_i = Some(i+1);
+        p.start_time = Some(now);
+        // Keep track of the last cell reached in case the path needs replanning
+        n.x = path[i].x as usize;
+        n.y = path[i].y as usize;
+    }
+
+    // Capped so it doesn't overshoot
+    let lerp_time = elapsed_time.min(travel_time);
+    Some(prev_pos + v.velocity * lerp_time)
+}
+
+/// Moves along a flow field, returns the new map position or None at the goal
+fn follow_field(v: &mut Velocity, n: &mut Navigator, field: &FlowField, delta_seconds: f32) -> Option<Vector2<f32>> {
+    // The field is rebuilt when towers change the map, so the cached step can
+    // lead into a cell that's now blocked. Start the step again if it moved
+    let step = field.step_from((n.x, n.y));
+    if n.next != step {
+        n.next = step;
+        n.progress = 0.;
+    }
+    let next = n.next?;
+
+    let prev_pos: Vector2<f32> = n.clone().into();
+    let next_pos = Vector2::new(next.0 as f32, next.1 as f32);
+
+    // Update the velocity angle and scale it to the right speed
+    v.velocity = next_pos - prev_pos;
+    set_magnitude(&mut v.velocity, v.speed);
+
+    let distance = prev_pos.metric_distance(&next_pos);
+    n.progress += v.speed * delta_seconds;
+
+    if n.progress >= distance {
+        // Arrived at the next cell, carry on from there next frame
+        n.progress -= distance;
+        n.x = next.0;
+        n.y = next.1;
+        n.next = field.step_from(next);
+        Some(next_pos)
+    } else {
+        Some(prev_pos + (next_pos - prev_pos) * (n.progress / distance))
+    }
+}
+
 impl<'s> System<'s> for NavigatorMover {
     type SystemData = (
+        Entities<'s>,
         WriteStorage<'s, Velocity>,
         WriteStorage<'s, Transform>,
         WriteStorage<'s, Path>,
         WriteStorage<'s, Navigator>,
-        Read<'s, Time>,
+        ReadStorage<'s, Map>,
+        Read<'s, FlowFields>,
+        ReadExpect<'s, MapConfig>,
+        Read<'s, SimClock>,
     );
 
-    fn run(&mut self, (mut velocities, mut transforms, mut paths, mut navigators, time): Self::SystemData) {
-        for (v, t, p, n) in (&mut velocities, &mut transforms, &mut paths, &mut navigators).join() {
-            if let (Some((path, _)), Some(i), start_time) = (&p.path, p.path_i, p.start_time) {
-                if i >= path.len() { 
-                    continue;
-                }
-                let next_pos: Vector2<f32> = path[i].into();
-                let prev_pos: Vector2<f32> = if i == 0 {
-                    n.clone().into()
-                } else {
-                    path[i - 1].into()
-                };
-
-                // Update the velocity angle
-                v.velocity = next_pos - prev_pos;
-
-                // Scale it to the right speed
-                set_magnitude(&mut v.velocity, v.speed);
-
-                let distance = prev_pos.metric_distance(&next_pos);
-                let travel_time = Duration::from_secs_f32(distance / v.speed);
-                let now = Instant::now();
-                let start_time = start_time.unwrap_or(now);
-                let elapsed_time = now - start_time;
-
-                if elapsed_time >= travel_time {
-                    p.path_i = Some(i+1);
-                    p.start_time = Some(now);
-                }
-
-                // Capped so it doesn't overshoot
-                let lerp_time = elapsed_time.min(travel_time);
-                let pos = prev_pos + v.velocity * lerp_time.as_secs_f32();
-
-                let (prev_x, prev_y) = {
-                    let t = t.translation();
-                    (t.x, t.y)
-                };
-                let mut screen_pos = iso_to_screen(pos);
-                screen_pos.z += CHARACTER_Z_OFFSET;
-                t.set_translation(screen_pos);
-
-                //TODO: This is so the heading system works, probably fix it so heading is aware of map space velocity instead of screen space?
-                v.velocity = Vector2::new(screen_pos.x, screen_pos.y) - Vector2::new(prev_x, prev_y);
-            } else {
-               t.prepend_translation_x(v.velocity.x * time.delta_seconds());
-               t.prepend_translation_y(v.velocity.y * time.delta_seconds());
+    fn run(&mut self, (entities, mut velocities, mut transforms, mut paths, mut navigators, maps, flow_fields, map_config, clock): Self::SystemData) {
+        let delta_seconds = clock.tick_seconds;
+
+        // Flow field navigators all head for the exit of the current map
+        let mut field = None;
+        if map_config.navigation == Navigation::FlowField {
+            for (e, m) in (&entities, &maps).join() {
+                if !entities.is_alive(e) { continue }
+                field = m.exit().and_then(|exit| flow_fields.get(exit));
+                break;
             }
         }
+
+        for (v, t, n, p) in (&mut velocities, &mut transforms, &mut navigators, (&mut paths).maybe()).join() {
+            let pos = match (p, field) {
+                (Some(p), _) if p.path.is_some() => follow_path(v, p, n, clock.elapsed()),
+                (_, Some(field)) => follow_field(v, n, field, delta_seconds),
+                _ => {
+                   t.prepend_translation_x(v.velocity.x * delta_seconds);
+                   t.prepend_translation_y(v.velocity.y * delta_seconds);
+                   continue;
+                },
+            };
+
+            // Stay put at the end of the route
+            let pos = match pos {
+                Some(pos) => pos,
+                None => continue,
+            };
+
+            let (prev_x, prev_y) = {
+                let t = t.translation();
+                (t.x, t.y)
+            };
+            let mut screen_pos = iso_to_screen(pos);
+            screen_pos.z += CHARACTER_Z_OFFSET;
+            t.set_translation(screen_pos);
+
+            //TODO: This is so the heading system works, probably fix it so heading is aware of map space velocity instead of screen space?
+            v.velocity = Vector2::new(screen_pos.x, screen_pos.y) - Vector2::new(prev_x, prev_y);
+        }
     }
 }
\ No newline at end of file
@@ -7,14 +7,21 @@ use amethyst::{
         WriteStorage,
         Entities,
         LazyUpdate,
+        ReadExpect,
     },
 };
 
-use crate::components::{
-    Navigator,
-    Map,
-    Path,
-    PathNode,
+use crate::{
+    components::{
+        Navigator,
+        Map,
+        Path,
+        PathNode,
+    },
+    config::{
+        Map as MapConfig,
+        map::Navigation,
+    },
 };
 
 pub struct PathFinder;
@@ -35,52 +42,33 @@ fn create_paths(
     }        
 
     if add_to.len() > 0 {
+        let mut map_entity = None;
         let mut map = None;
-        let mut hack_map = None;
 
         // Find an active map
         for (e, m) in (entities, maps).join() {
             if entities.is_alive(e) {
-                map = Some(e);
-                hack_map = Some(m);
+                map_entity = Some(e);
+                map = Some(m);
                 break;
             }
         }
 
         // If we found a map (if not, we'll just end up here later)
-        if let (Some(map), Some(hack_map)) = (map, hack_map) {
-            let mut x0 = 0;
-            let mut y0 = 0;
-
-            while hack_map.is_wall(x0, y0) {
-                x0 += 1;
-                if x0 > hack_map.width() - 1 {
-                    x0 = 0;
-                    y0 += 1;
-                }
-            }
-
-            let mut x1 = hack_map.width() - 1;
-            let mut y1 = hack_map.height() - 1;
-
-            while hack_map.is_wall(x1, y1) {
-                if x1 == 1 {
-                    x1 = hack_map.width() - 1;
-                    y1 -= 1;
-                } else {
-                    x1 -= 1;
+        if let (Some(map_entity), Some(map)) = (map_entity, map) {
+            if let Some((x, y)) = map.exit() {
+                let objective = PathNode::new(x as i32, y as i32);
+                for (e, n) in add_to {
+                    let origin = PathNode::new(n.x as i32, n.y as i32);
+                    let path = Path::new(
+                        map_entity,
+                        objective,
+                        origin,
+                    );
+                    lazy_update.insert(e, path);
                 }
-            }
-
-            let objective = PathNode::new(x1 as i32, y1 as i32);
-            for (e, n) in add_to {
-                let origin = PathNode::new(n.x as i32, n.y as i32);
-                let path = Path::new(
-                    map,
-                    origin,
-                    objective,
-                );
-                lazy_update.insert(e, path);
+            } else {
+                log::error!("Map {:?} has no exit to path to", map_entity);
             }
         }
     }
@@ -111,16 +99,21 @@ fn remove_dead_paths<'s>(
 /// Run pathfinding on path components
 fn run_pathfinding(
     entities: &Entities,
+    navigators: &ReadStorage<Navigator>,
     paths: &mut WriteStorage<Path>,
     maps: &ReadStorage<Map>,
 ) {
-    for (e, p) in (entities, paths).join() {
+    for (e, n, p) in (entities, navigators, paths).join() {
         // No point if either owner is dead
         if !entities.is_alive(e) || !entities.is_alive(p.map_entity) {
             continue;
         }
 
         if let Some(map) = maps.get(p.map_entity) {
+            // The way may have been blocked since the path was found
+            if p.path.is_some() && p.map_version != map.version() {
+                p.replan(n.clone().into());
+            }
             if p.path.is_none() {
                 p.run(map);
             }
@@ -139,11 +132,15 @@ impl<'s> System<'s> for PathFinder {
         WriteStorage<'s, Path>,
         Read<'s, LazyUpdate>,
         ReadStorage<'s, Map>,
+        ReadExpect<'s, MapConfig>,
     );
 
-    fn run(&mut self, (entities, navigators, mut paths, lazy_update, maps): Self::SystemData) {
-        create_paths(&entities, &navigators, &paths, &maps, &lazy_update);
+    fn run(&mut self, (entities, navigators, mut paths, lazy_update, maps, map_config): Self::SystemData) {
+        // Flow field navigators don't need their own path
+        if map_config.navigation == Navigation::AStar {
+            create_paths(&entities, &navigators, &paths, &maps, &lazy_update);
+        }
         paths = remove_dead_paths(&entities, paths);
-        run_pathfinding(&entities, &mut paths, &maps);
+        run_pathfinding(&entities, &navigators, &mut paths, &maps);
     }
 }
\ No newline at end of file
@@ -0,0 +1,128 @@
+use amethyst::{
+    core::transform::Transform,
+    ecs::prelude::{
+        Join, 
+        ReadStorage, 
+        System, 
+        WriteStorage,
+        Entities,
+        ReadExpect,
+        Write,
+        WriteExpect,
+        Read,
+        LazyUpdate,
+    },
+};
+use crate::{
+    components::{
+        Animation,
+        Creep,
+        Dying,
+        Health,
+        Projectile,
+        Velocity,
+    },
+    resources::{
+        Gold,
+        SimClock,
+        Stats,
+    },
+    util::{
+        iso_distance,
+        spawn::spawn_damage_number,
+    },
+    config::{
+        tower::ProjectileKind,
+        Creep as CreepConfig,
+    },
+};
+
+/// How close (in map cells) a projectile has to get to a creep to hit it
+const HIT_RADIUS: f32 = 0.5;
+
+pub struct ProjectileHit;
+
+impl<'s> System<'s> for ProjectileHit {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, Projectile>,
+        ReadStorage<'s, Transform>,
+        WriteStorage<'s, Creep>,
+        WriteStorage<'s, Health>,
+        WriteStorage<'s, Velocity>,
+        WriteStorage<'s, Animation>,
+        WriteStorage<'s, Dying>,
+        ReadExpect<'s, CreepConfig>,
+        WriteExpect<'s, Gold>,
+        Write<'s, Stats>,
+        Read<'s, LazyUpdate>,
+        Read<'s, SimClock>,
+    );
+
+    fn run(&mut self, (entities, projectiles, transforms, mut creeps, mut healths, mut velocities, mut animations, mut dying, creep_config, mut gold, mut stats, lazy_update, clock): Self::SystemData) {
+        let mut killed = Vec::new();
+
+        for (projectile_entity, projectile, projectile_transform) in (&entities, &projectiles, &transforms).join() {
+            // Skip any dead ones
+            if !entities.is_alive(projectile_entity) { continue }
+
+            // Find the first creep the projectile is touching
+            let mut hit = None;
+            for (creep_entity, creep, health, creep_transform) in (&entities, &creeps, &healths, &transforms).join() {
+                // Skip creeps that are already dying
+                if !entities.is_alive(creep_entity) || health.is_dead() { continue }
+
+                let distance = iso_distance(projectile_transform.translation(), creep_transform.translation());
+                if distance <= HIT_RADIUS * creep.size {
+                    hit = Some((creep_entity, *creep_transform.translation()));
+                    break;
+                }
+            }
+
+            if let Some((hit_entity, hit_position)) = hit {
+                for (creep_entity, creep, health, creep_transform) in (&entities, &creeps, &mut healths, &transforms).join() {
+                    if !entities.is_alive(creep_entity) || health.is_dead() { continue }
+
+                    let damaged = match projectile.kind {
+                        ProjectileKind::Bullet => creep_entity == hit_entity,
+                        ProjectileKind::Splash { radius } => iso_distance(&hit_position, creep_transform.translation()) <= radius,
+                    };
+                    if !damaged { continue }
+
+                    let damage = creep.damage_taken(projectile.damage);
+                    health.health -= damage;
+                    // Armour soaked up the whole hit, a 0 would just be noise
+                    if damage > 0. {
+                        spawn_damage_number(&entities, &lazy_update, creep_transform.translation(), damage);
+                    }
+                    if health.is_dead() {
+                        killed.push(creep_entity);
+                    }
+                }
+
+                // Projectiles are used up by the first hit
+                entities.delete(projectile_entity).expect("Failed to delete entity");
+            }
+        }
+
+        for e in killed {
+            // Pay out the bounty before the creep component goes
+            if let Some(archetype) = creeps.get(e).and_then(|c| creep_config.archetype(&c.archetype)) {
+                gold.gold += archetype.bounty;
+                stats.gold_earned += archetype.bounty;
+            }
+            stats.kills += 1;
+
+            // Stops it moving and being targeted, Murder deletes it once it's been dying long enough
+            velocities.remove(e);
+            creeps.remove(e);
+            dying.insert(e, Dying::new(&clock)).expect("Failed to insert Dying component");
+
+            if let Some(anim) = animations.get_mut(e) {
+                if let Some(death) = anim.current.and_then(|a| a.death()) {
+                    anim.next = Some(death);
+                }
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -1,8 +1,5 @@
 use amethyst::{
-    core::{
-        timing::Time, 
-        transform::Transform,
-    },
+    core::transform::Transform,
     ecs::prelude::{
         Join, 
         Read, 
@@ -18,6 +15,7 @@ use crate::{
         Projectile,
         Map,
     },
+    resources::SimClock,
 };
 
 pub struct ProjectileMover;
@@ -29,18 +27,18 @@ impl<'s> System<'s> for ProjectileMover {
         WriteStorage<'s, Transform>,
         ReadStorage<'s, Projectile>,
         ReadStorage<'s, Map>,
-        Read<'s, Time>,
+        Read<'s, SimClock>,
     );
 
-    fn run(&mut self, (entities, velocities, mut transforms, projectiles, maps, time): Self::SystemData) {
+    fn run(&mut self, (entities, velocities, mut transforms, projectiles, maps, clock): Self::SystemData) {
         let mut map = None;
         for m in (&maps).join() {
             map = Some(m);
             break;
         }
         for (e, v, t, _) in (&entities, &velocities, &mut transforms, &projectiles).join() {
-            t.prepend_translation_x(v.velocity.x * time.delta_seconds());
-            t.prepend_translation_y(v.velocity.y * time.delta_seconds());
+            t.prepend_translation_x(v.velocity.x * clock.tick_seconds);
+            t.prepend_translation_y(v.velocity.y * clock.tick_seconds);
 
             if let Some(map) = map {
                 if let Some(cell) = map.world_to_cell_index(t.translation().xy()) {
@@ -1,68 +1,80 @@
-use amethyst::{
-    core::timing::Time,
-    ecs::prelude::{
-        Read, 
-        System, 
-        Entities, 
-        LazyUpdate,
-        ReadStorage,
-        Join,
-        ReadExpect,
-    },
-    utils::fps_counter::FpsCounter,
+use amethyst::ecs::prelude::{
+    Read, 
+    System, 
+    Entities, 
+    LazyUpdate,
+    ReadStorage,
+    Join,
+    ReadExpect,
+    Write,
+    WriteExpect,
 };
 use crate::{
-    components::Map,
-    resources::Sprites,
-    util::spawn::{ spawn_creep, spawn_tower, random_room },
+    components::{
+        Map,
+        Creep,
+    },
+    resources::{
+        Sprites,
+        WaveStatus,
+        WaveProgress,
+        EntryProgress,
+        SimClock,
+        SimRng,
+    },
+    util::spawn::{ spawn_creep, random_entrance },
     config::{
         Spawner as SpawnerConfig,
-        Tower as TowerConfig,
+        Creep as CreepConfig,
+        Waves as WavesConfig,
+        waves::{
+            WaveEntry,
+            SpawnPoint,
+        },
     },
 };
-use std::usize;
 
+/// Spawns the creeps in waves.ron, keeping track of where it's up to in WaveProgress
 #[derive(Default)]
-pub struct Spawner {
-    pub elapsed: f32,
-    pub creep_count: usize,
-    pub tower_count: usize,
-}
+pub struct Spawner;
 
-impl Spawner {
-    fn spawn_creep(
-        &mut self,
-        entities: &Entities,
-        lazy_update: &LazyUpdate,
-        sprites_resource: &Read<Sprites>,
-        map: &Map,
-        spawner_config: &SpawnerConfig,
-    ) {
-        if self.creep_count < spawner_config.max_spawns.unwrap_or(usize::MAX) {
-            if let Ok(room) = random_room(map) {
-                spawn_creep(entities, lazy_update, sprites_resource, room, spawner_config);
-                self.creep_count += 1;
+fn spawn_cell(rng: &mut SimRng, map: &Map, spawn_point: SpawnPoint) -> Option<(usize, usize)> {
+    match spawn_point {
+        SpawnPoint::Random => random_entrance(&mut rng.rng, map).ok(),
+        SpawnPoint::Entrance(i) => {
+            let entrance = map.entrances().get(i).cloned();
+            if entrance.is_none() {
+                log::warn!("Spawn point entrance {} doesn't exist, using a random one", i);
+                random_entrance(&mut rng.rng, map).ok()
+            } else {
+                entrance
             }
-        }
+        },
     }
+}
 
-    fn spawn_towers(
+impl Spawner {
+    fn spawn_creep(
         &mut self,
+        rng: &mut SimRng,
         entities: &Entities,
         lazy_update: &LazyUpdate,
-        sprites_resource: &Read<Sprites>,
+        sprites_resource: Option<&Sprites>,
         map: &Map,
+        entry: &WaveEntry,
         spawner_config: &SpawnerConfig,
-        tower_config: &TowerConfig,
+        creep_config: &CreepConfig,
     ) {
-        let rooms = map.rooms();
-        while self.tower_count < spawner_config.max_towers.unwrap_or(5).min(rooms.len()) {
-            if rooms.len() == 0 { return } 
+        let archetype = match creep_config.archetype(&entry.creep) {
+            Some(archetype) => archetype,
+            None => {
+                log::warn!("Wave entry has unknown creep archetype {}", entry.creep);
+                return;
+            },
+        };
 
-            let room = &rooms[self.tower_count % rooms.len()];
-            spawn_tower(entities, lazy_update, sprites_resource, room, tower_config);
-
-            self.tower_count += 1;
+        if let Some(cell) = spawn_cell(rng, map, entry.spawn_point) {
+            spawn_creep(&mut rng.rng, entities, lazy_update, sprites_resource, cell, archetype, spawner_config);
         }
     }
 }
@@ -72,11 +84,15 @@ impl<'s> System<'s> for Spawner {
         Entities<'s>,
         Read<'s, LazyUpdate>,
         Option<Read<'s, Sprites>>,
-        Read<'s, Time>,
-        Read<'s, FpsCounter>,
+        Read<'s, SimClock>,
+        WriteExpect<'s, SimRng>,
         ReadStorage<'s, Map>,
+        ReadStorage<'s, Creep>,
         ReadExpect<'s, SpawnerConfig>,
-        ReadExpect<'s, TowerConfig>,
+        ReadExpect<'s, CreepConfig>,
+        ReadExpect<'s, WavesConfig>,
+        Write<'s, WaveStatus>,
+        Write<'s, WaveProgress>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
@@ -84,17 +100,17 @@ impl<'s> System<'s> for Spawner {
             entities, 
             lazy_update,
             sprites_resource,
-            time,
-            fps,
+            clock,
+            mut rng,
             maps,
+            creeps,
             spawner_config,
-            tower_config,
+            creep_config,
+            waves_config,
+            mut wave_status,
+            mut wave_progress,
         ) = data;
 
-        if sprites_resource.is_none() { 
-            return;
-        }
-
         let mut map = None;
 
         for (e, m) in (&entities, &maps).join() {
@@ -108,34 +124,78 @@ impl<'s> System<'s> for Spawner {
             return;
         }
 
-        if self.tower_count == 0 {
-            self.spawn_towers(
-                &entities,
-                &lazy_update,
-                sprites_resource.as_ref().unwrap(),
-                &map.unwrap(),
-                &spawner_config,
-                &tower_config,
-            );
+        let waves = &waves_config.waves;
+        wave_status.wave_count = waves.len();
+
+        if wave_status.cleared {
+            return;
         }
 
-        let delta_seconds = time.delta_seconds();
-        self.elapsed += delta_seconds;
+        let delta_seconds = clock.tick_seconds;
+        // Reborrowed so its fields can be borrowed separately
+        let wave_progress = &mut *wave_progress;
 
-        if self.elapsed >= spawner_config.spawn_delay {
-            self.elapsed -= spawner_config.spawn_delay ;
-            let fps = fps.sampled_fps();
-            if fps > 59.5 {
+        if !wave_progress.spawning {
+            if wave_progress.wave_i >= waves.len() {
+                // Everything has spawned, wait for the last creeps to go
+                if (&entities, &creeps).join().count() == 0 {
+                    log::info!("All {} waves cleared", waves.len());
+                    wave_status.cleared = true;
+                }
+                return;
+            }
+
+            let countdown = wave_progress.countdown.get_or_insert(waves[wave_progress.wave_i].delay);
+            *countdown -= delta_seconds;
+            wave_status.next_wave_in = Some(countdown.max(0.));
+
+            if *countdown <= 0. {
+                log::info!("Starting wave {} of {}", wave_progress.wave_i + 1, waves.len());
+                wave_progress.countdown = None;
+                wave_progress.spawning = true;
+                // Primed so the first creep of each entry spawns straight away
+                wave_progress.entries = waves[wave_progress.wave_i].entries.iter().map(|e| EntryProgress {
+                    spawned: 0,
+                    elapsed: e.interval.unwrap_or(spawner_config.spawn_delay),
+                }).collect();
+                wave_status.wave = wave_progress.wave_i + 1;
+                wave_status.next_wave_in = None;
+            }
+        }
+
+        if wave_progress.spawning {
+            let mut done = true;
+            let mut to_spawn = Vec::new();
+            for (entry, progress) in waves[wave_progress.wave_i].entries.iter().zip(wave_progress.entries.iter_mut()) {
+                if progress.spawned >= entry.count { continue }
+                done = false;
+
+                let interval = entry.interval.unwrap_or(spawner_config.spawn_delay);
+                progress.elapsed += delta_seconds;
+                while progress.elapsed >= interval && progress.spawned < entry.count {
+                    progress.elapsed -= interval;
+                    progress.spawned += 1;
+                    to_spawn.push(entry);
+                }
+            }
+
+            for entry in to_spawn {
                 self.spawn_creep(
+                    &mut rng,
                     &entities,
                     &lazy_update,
-                    sprites_resource.as_ref().unwrap(),
+                    sprites_resource.as_ref().map(|s| &**s),
                     &map.unwrap(),
+                    entry,
                     &spawner_config,
+                    &creep_config,
                 );
             }
+
+            if done {
+                wave_progress.spawning = false;
+                wave_progress.wave_i += 1;
+            }
         }
-        
     }
-}
-
+}
\ No newline at end of file
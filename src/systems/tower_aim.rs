@@ -2,6 +2,7 @@ use amethyst::{
     core::transform::Transform,
     ecs::prelude::{
         Join, 
+        Read,
         ReadStorage, 
         System, 
         WriteStorage,
@@ -12,10 +13,19 @@ use std::f32;
 use crate::{
     components::{
         Creep,
+        Health,
         Tower,
         Map,
+        Navigator,
+        Path,
+        TargetingMode,
     },
-    util::iso_distance,
+    resources::{
+        CreepGrid,
+        FlowFields,
+        Visibility,
+    },
+    util::screen_to_iso,
 };
 
 pub struct TowerAim;
@@ -27,9 +37,15 @@ impl<'s> System<'s> for TowerAim {
         WriteStorage<'s, Tower>,
         ReadStorage<'s, Transform>,
         ReadStorage<'s, Map>,
+        ReadStorage<'s, Health>,
+        ReadStorage<'s, Navigator>,
+        ReadStorage<'s, Path>,
+        Read<'s, FlowFields>,
+        Read<'s, CreepGrid>,
+        Read<'s, Visibility>,
     );
 
-    fn run(&mut self, (entities, creeps, mut towers, transforms, maps): Self::SystemData) {
+    fn run(&mut self, (entities, creeps, mut towers, transforms, maps, healths, navigators, paths, flow_fields, grid, visibility): Self::SystemData) {
         let mut map = None;
         for (entity, m) in (&entities, &maps).join() {
             if entities.is_alive(entity) { 
@@ -39,36 +55,60 @@ impl<'s> System<'s> for TowerAim {
         }
 
         if let Some(map) = map {
+            let field = map.exit().and_then(|exit| flow_fields.get(exit));
+
+            // How far a creep still has to go to reach the exit
+            let remaining = |creep_entity| -> f32 {
+                if let Some(remaining) = paths.get(creep_entity).and_then(|p| p.remaining()) {
+                    return remaining as f32;
+                }
+                navigators.get(creep_entity)
+                    .and_then(|n| field?.cost((n.x, n.y)))
+                    .map_or(f32::MAX, |cost| cost as f32)
+            };
+
             for (tower_entity, tower, tower_transform) in (&entities, &mut towers, &transforms).join() {
                 // Skip any dead ones
                 if !entities.is_alive(tower_entity) { continue }
 
-                let mut best_range = f32::MAX;
+                // Lower scores are better
+                let mut best_score = f32::MAX;
                 tower.target = None;
 
-                for (creep_entity, _, creep_transform) in (&entities, &creeps, &transforms).join() {
-                    // Skip any dead ones
-                    if !entities.is_alive(creep_entity) { continue }
-
-                    // How far away is the creep
-                    let distance = iso_distance(tower_transform.translation(), creep_transform.translation());
+                // Only creeps in grid buckets near the tower are candidates
+                let tower_pos = screen_to_iso(tower_transform.translation().xy());
+                for (creep_entity, distance) in grid.query(tower_pos, tower.range) {
+                    // Skip any dead ones, or creeps killed since the grid was built
+                    if !entities.is_alive(creep_entity) || !creeps.contains(creep_entity) { continue }
 
-                    // Check if it's in range
-                    if distance > tower.range { continue }
+                    let creep_transform = match transforms.get(creep_entity) {
+                        Some(transform) => transform,
+                        None => continue,
+                    };
 
-                    // Check line of sight
-                    if let Some(origin) = map.world_to_cell_index(tower_transform.translation().xy()) {
-                        if let Some(target) = map.world_to_cell_index(creep_transform.translation().xy()) {
-                            if !map.line_of_sight(origin, target) { continue }
-                        } else {
-                            log::warn!("Creep {:?} outside world bounds", creep_entity);
-                        }
+                    // Check line of sight, falling back to a ray if the tower's
+                    // visible cells haven't been built yet
+                    if let Some(target) = map.world_to_cell_index(creep_transform.translation().xy()) {
+                        let visible = match visibility.get(tower.cell) {
+                            Some(visible) if visible.range == tower.range => visible.can_see(target),
+                            _ => map.line_of_sight(tower.cell, target),
+                        };
+                        if !visible { continue }
                     } else {
-                        log::warn!("Tower {:?} outside world bounds", tower_entity);
+                        log::warn!("Creep {:?} outside world bounds", creep_entity);
                     }
 
-                    if distance < best_range {
-                        best_range = distance;
+                    let health = healths.get(creep_entity).map_or(0., |h| h.health);
+                    let score = match tower.targeting {
+                        TargetingMode::First => remaining(creep_entity),
+                        TargetingMode::Last => -remaining(creep_entity),
+                        TargetingMode::Closest => distance,
+                        TargetingMode::Strongest => -health,
+                        TargetingMode::Weakest => health,
+                    };
+
+                    if tower.target.is_none() || score < best_score {
+                        best_score = score;
                         tower.target = Some(creep_entity);
                     }
                 }
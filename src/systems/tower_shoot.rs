@@ -1,5 +1,5 @@
 use amethyst::{
-    core::{ timing::Time, transform::Transform },
+    core::transform::Transform,
     ecs::prelude::{
         Join, 
         ReadStorage, 
@@ -9,6 +9,7 @@ use amethyst::{
         Read,
         LazyUpdate,
         ReadExpect,
+        WriteExpect,
     },
 };
 use crate::{
@@ -16,17 +17,19 @@ use crate::{
         Creep,
         Tower,
         Velocity,
+        Projectile,
     },
     util::{
         spawn::spawn_projectile,
         math::intercept,
     },
+    resources::{
+        SimClock,
+        SimRng,
+    },
     config::Tower as TowerConfig,
 };
-use rand::{
-    thread_rng,
-    Rng,
-};
+use rand::Rng;
 
 pub struct TowerShoot;
 
@@ -37,13 +40,14 @@ impl<'s> System<'s> for TowerShoot {
         WriteStorage<'s, Tower>,
         ReadStorage<'s, Transform>,
         ReadStorage<'s, Velocity>,
-        Read<'s, Time>,
+        Read<'s, SimClock>,
         Read<'s, LazyUpdate>,
         ReadExpect<'s, TowerConfig>,
+        WriteExpect<'s, SimRng>,
     );
 
-    fn run(&mut self, (entities, _creeps, mut towers, transforms, velocities, time, lazy_update, config): Self::SystemData) {
-        let delta_seconds = time.delta_seconds();
+    fn run(&mut self, (entities, _creeps, mut towers, transforms, velocities, clock, lazy_update, config, mut rng): Self::SystemData) {
+        let delta_seconds = clock.tick_seconds;
 
         for (tower_entity, tower, tower_transform) in (&entities, &mut towers, &transforms).join() {
             // Skip dead towers
@@ -52,6 +56,14 @@ impl<'s> System<'s> for TowerShoot {
             // Advance time
             tower.elapsed += delta_seconds;
 
+            let stats = match config.archetype(&tower.archetype) {
+                Some(archetype) => archetype.stats(tower.level),
+                None => {
+                    log::warn!("Tower {:?} has unknown archetype {}", tower_entity, tower.archetype);
+                    continue;
+                },
+            };
+
             // If the tower is ready to fire and has a target
             if tower.elapsed >= tower.frequency && tower.target.is_some() {
                 // Skip dead targets
@@ -64,7 +76,7 @@ impl<'s> System<'s> for TowerShoot {
                             origin,
                             target,
                             target_velocity.velocity, 
-                            config.projectile_speed,
+                            stats.projectile_speed,
                         ) {
                             tower.elapsed = 0.;
 
@@ -72,9 +84,9 @@ impl<'s> System<'s> for TowerShoot {
                             let dx = solution.x - origin.x;
                             let dy = solution.y - origin.y;
 
-                            let jitter = if config.aim_jitter > 0. {
-                                let j = config.aim_jitter * 0.5;
-                                thread_rng().gen_range(-j, j)
+                            let jitter = if stats.aim_jitter > 0. {
+                                let j = stats.aim_jitter * 0.5;
+                                rng.rng.gen_range(-j, j)
                             } else {
                                 0.
                             };
@@ -87,9 +99,10 @@ impl<'s> System<'s> for TowerShoot {
                                 tower_transform.clone(),
                                 Velocity::new(
                                     // *60 is because velocity is scaled by seconds elapsed
-                                    angle.cos() * config.projectile_speed * 60.,
-                                    angle.sin() * config.projectile_speed * 60.,
+                                    angle.cos() * stats.projectile_speed * 60.,
+                                    angle.sin() * stats.projectile_speed * 60.,
                                 ),
+                                Projectile::new(stats.damage, stats.projectile),
                             );
                         }
                     }
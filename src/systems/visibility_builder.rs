@@ -0,0 +1,48 @@
+use amethyst::ecs::prelude::{
+    Join, 
+    ReadStorage, 
+    System, 
+    Write,
+    Entities,
+};
+use std::collections::HashSet;
+use crate::{
+    components::{
+        Map,
+        Tower,
+    },
+    resources::Visibility,
+};
+
+/// Keeps the Visibility sets in step with the map and the towers on it
+pub struct VisibilityBuilder;
+
+impl<'s> System<'s> for VisibilityBuilder {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, Map>,
+        ReadStorage<'s, Tower>,
+        Write<'s, Visibility>,
+    );
+
+    fn run(&mut self, (entities, maps, towers, mut visibility): Self::SystemData) {
+        for (map_entity, map) in (&entities, &maps).join() {
+            if !entities.is_alive(map_entity) { continue }
+
+            if !visibility.is_current(map_entity, map) {
+                visibility.reset(map_entity, map);
+                log::info!("Reset tower visibility for {:?}", map_entity);
+            }
+
+            let mut tower_cells = HashSet::new();
+            for (tower_entity, tower) in (&entities, &towers).join() {
+                if !entities.is_alive(tower_entity) { continue }
+
+                visibility.update(map, tower.cell, tower.range);
+                tower_cells.insert(tower.cell);
+            }
+            visibility.retain(|cell| tower_cells.contains(&cell));
+            break;
+        }
+    }
+}
\ No newline at end of file
@@ -1,5 +1,6 @@
 pub const FLOOR_Z_OFFSET: f32 = -0.1;
 pub const WALL_Z_OFFSET: f32 = 0.;
 pub const CHARACTER_Z_OFFSET: f32 = 0.;
+pub const HUD_Z: f32 = 9.8;
 pub const DEBUG_Z: f32 = 9.9;
 pub const CAMERA_Z: f32 = 10.;
\ No newline at end of file
@@ -0,0 +1,89 @@
+use image::{
+    Rgb,
+    RgbImage,
+};
+use serde::{Serialize, Deserialize};
+use crate::components::{
+    Map,
+    map::{
+        MapObject,
+        TileState,
+    },
+};
+
+const WALL_COLOR: [u8; 3] = [40, 40, 40];
+const DOOR_COLOR: [u8; 3] = [200, 140, 40];
+const CORRIDOR_COLOR: [u8; 3] = [150, 150, 150];
+const ROOM_COLOR: [u8; 3] = [230, 230, 230];
+const ENTRANCE_COLOR: [u8; 3] = [40, 200, 40];
+const EXIT_COLOR: [u8; 3] = [220, 40, 40];
+
+/// Inclusive range of map cells covered by a room, door or corridor
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CellRect {
+    pub start: (usize, usize),
+    pub end: (usize, usize),
+}
+
+impl From<&MapObject> for CellRect {
+    fn from(object: &MapObject) -> Self {
+        Self {
+            start: (object.start().x, object.start().y),
+            end: (object.end().x, object.end().y),
+        }
+    }
+}
+
+/// The layout of a generated map, for saving alongside bug reports
+#[derive(Debug, Deserialize, Serialize)]
+pub struct MapSummary {
+    pub seed: u64,
+    pub width: u32,
+    pub height: u32,
+    pub entrances: Vec<(usize, usize)>,
+    pub exit: Option<(usize, usize)>,
+    pub rooms: Vec<CellRect>,
+    pub doors: Vec<CellRect>,
+    pub corridors: Vec<CellRect>,
+}
+
+impl MapSummary {
+    pub fn new(map: &Map) -> Self {
+        Self {
+            seed: map.seed(),
+            width: map.width(),
+            height: map.height(),
+            entrances: map.entrances().to_vec(),
+            exit: map.exit(),
+            rooms: map.rooms().iter().map(CellRect::from).collect(),
+            doors: map.doors().iter().map(CellRect::from).collect(),
+            corridors: map.corridors().iter().map(CellRect::from).collect(),
+        }
+    }
+}
+
+/// One pixel per cell, laid out the same way as Map::to_ascii
+pub fn map_image(map: &Map) -> RgbImage {
+    let (width, height) = (map.width(), map.height());
+    let mut image = RgbImage::new(height, width);
+
+    for x in 0..width as usize {
+        for y in 0..height as usize {
+            let cell = (x, y);
+            let color = if map.entrances().contains(&cell) {
+                ENTRANCE_COLOR
+            } else if map.exit() == Some(cell) {
+                EXIT_COLOR
+            } else {
+                match map.tile(cell) {
+                    Some(TileState::Room(_)) => ROOM_COLOR,
+                    Some(TileState::Corridor(_)) => CORRIDOR_COLOR,
+                    Some(TileState::Door) => DOOR_COLOR,
+                    _ => WALL_COLOR,
+                }
+            };
+            image.put_pixel(height - 1 - y as u32, width - 1 - x as u32, Rgb(color));
+        }
+    }
+    image
+}
\ No newline at end of file
@@ -7,4 +7,16 @@ pub mod constants;
 mod iso;
 pub use iso::*;
 
-pub mod spawn;
\ No newline at end of file
+mod screen;
+pub use screen::*;
+
+pub mod spawn;
+
+mod simulation;
+pub use simulation::*;
+
+pub mod map_export;
+
+pub mod save;
+
+pub mod replay;
\ No newline at end of file
@@ -0,0 +1,120 @@
+use amethyst::{
+    ApplicationBuilder,
+    DataDispose,
+    ecs::prelude::{
+        World,
+        WorldExt,
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{
+        Path as FilePath,
+        PathBuf,
+    },
+};
+use crate::{
+    config::{
+        Game as GameConfig,
+        Waves as WavesConfig,
+    },
+    resources::{
+        CommandLog,
+        Playback,
+        SimClock,
+        TimedCommand,
+    },
+    util::save::Error,
+};
+
+/// The configs a game started with and every command carried out in it
+#[derive(Clone, Deserialize, Serialize)]
+pub struct Replay {
+    /// The map seed, which also seeds the SimRng
+    pub seed: u64,
+    pub config: GameConfig,
+    pub waves: WavesConfig,
+    pub commands: Vec<TimedCommand>,
+    /// SimClock::tick when the replay was written
+    pub end_tick: u64,
+}
+
+/// Added when the game is being recorded, the commands are taken from the CommandLog when it's written
+pub struct Recording {
+    pub path: PathBuf,
+    pub replay: Replay,
+}
+
+impl Replay {
+    pub fn new(seed: u64, config: GameConfig, waves: WavesConfig) -> Self {
+        Self {
+            seed,
+            config,
+            waves,
+            commands: Vec::new(),
+            end_tick: 0,
+        }
+    }
+
+    /// The configs to play the replay with and the commands to play back
+    pub fn into_parts(self) -> (GameConfig, WavesConfig, Vec<TimedCommand>) {
+        let mut config = self.config;
+        config.map.seed = Some(self.seed);
+        (config, self.waves, self.commands)
+    }
+
+    pub fn write(&self, path: &FilePath) -> Result<(), Error> {
+        let ron = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default()).map_err(Error::Serialize)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(Error::Io)?;
+        }
+        fs::write(path, ron).map_err(Error::Io)
+    }
+
+    pub fn read(path: &FilePath) -> Result<Self, Error> {
+        let ron = fs::read_to_string(path).map_err(Error::Io)?;
+        ron::de::from_str(&ron).map_err(Error::Deserialize)
+    }
+}
+
+/// Adds the Playback of commands and the Recording if record_path is set. Picks the
+/// game's seed, which is recorded and passed on to register_game
+pub fn register<S, T, E, X>(
+    builder: ApplicationBuilder<S, T, E, X>,
+    game_config: &GameConfig,
+    waves_config: &WavesConfig,
+    commands: Vec<TimedCommand>,
+    record_path: Option<PathBuf>,
+) -> (ApplicationBuilder<S, T, E, X>, u64)
+where
+    T: DataDispose + 'static,
+{
+    let seed = game_config.map.seed.unwrap_or_else(rand::random);
+
+    let mut builder = builder.with_resource(Playback::new(commands));
+    if let Some(path) = record_path {
+        builder = builder.with_resource(Recording {
+            path,
+            replay: Replay::new(seed, game_config.clone(), waves_config.clone()),
+        });
+    }
+    (builder, seed)
+}
+
+/// Writes the game so far to the Recording's path if it's being recorded
+pub fn write_recording(world: &World) {
+    let recording = match world.try_fetch::<Recording>() {
+        Some(recording) => recording,
+        None => return,
+    };
+
+    let mut replay = recording.replay.clone();
+    replay.commands = world.read_resource::<CommandLog>().commands.clone();
+    replay.end_tick = world.read_resource::<SimClock>().tick;
+
+    match replay.write(&recording.path) {
+        Ok(()) => log::info!("Wrote replay of {} commands to {:?}", replay.commands.len(), recording.path),
+        Err(e) => log::error!("Failed to write replay {:?}: {}", recording.path, e),
+    }
+}
\ No newline at end of file
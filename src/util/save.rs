@@ -0,0 +1,420 @@
+use amethyst::{
+    core::{
+        transform::Transform,
+        math::{
+            Vector2,
+            Vector3,
+        },
+    },
+    utils::application_root_dir,
+    ecs::prelude::{
+        Builder,
+        Entity,
+        Join,
+        LazyUpdate,
+        World,
+        WorldExt,
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt,
+    fs,
+    io,
+    path::{
+        Path as FilePath,
+        PathBuf,
+    },
+};
+use crate::{
+    components::{
+        Age,
+        Creep,
+        DamageNumber,
+        Dying,
+        Health,
+        Map,
+        Navigator,
+        Path,
+        PathNode,
+        Projectile,
+        TargetingMode,
+        Tower,
+        Velocity,
+    },
+    config::{
+        Creep as CreepConfig,
+        Map as MapConfig,
+        Spawner as SpawnerConfig,
+        Tower as TowerConfig,
+    },
+    resources::{
+        Commands,
+        CommandLog,
+        Playback,
+        Gold,
+        Lives,
+        SimClock,
+        SimRng,
+        Sprites,
+        Stats,
+        WaveProgress,
+        WaveStatus,
+    },
+    util::{
+        replay::Recording,
+        spawn::{
+            spawn_creep,
+            spawn_projectile,
+            spawn_tower,
+        },
+    },
+};
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Serialize(ron::ser::Error),
+    Deserialize(ron::de::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "{}", e),
+            Error::Serialize(e) => write!(f, "{}", e),
+            Error::Deserialize(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TowerSave {
+    pub archetype: String,
+    pub cell: (usize, usize),
+    pub level: usize,
+    pub value: u32,
+    pub targeting: TargetingMode,
+    pub elapsed: f32,
+    /// Index into SaveGame::creeps
+    pub target: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PathSave {
+    pub objective: PathNode,
+    pub origin: PathNode,
+    pub path: Option<(Vec<PathNode>, i32)>,
+    pub path_i: Option<usize>,
+    pub start_time: Option<f32>,
+    /// False if the map changed after the path was found, so it gets replanned
+    pub current: bool,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct CreepSave {
+    pub archetype: String,
+    pub translation: [f32; 3],
+    pub velocity: [f32; 2],
+    pub speed: f32,
+    pub health: Health,
+    pub age: Age,
+    pub navigator: Navigator,
+    pub path: Option<PathSave>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ProjectileSave {
+    pub translation: [f32; 3],
+    pub velocity: [f32; 2],
+    pub speed: f32,
+    pub projectile: Projectile,
+}
+
+/// Everything needed to carry on with a game in progress
+#[derive(Deserialize, Serialize)]
+pub struct SaveGame {
+    /// The map is generated again from its config and seed
+    pub map: MapConfig,
+    pub map_seed: u64,
+    pub map_version: u32,
+    pub towers: Vec<TowerSave>,
+    pub creeps: Vec<CreepSave>,
+    pub projectiles: Vec<ProjectileSave>,
+    pub gold: Gold,
+    pub lives: Lives,
+    pub stats: Stats,
+    pub wave_status: WaveStatus,
+    pub wave_progress: WaveProgress,
+    /// SimClock::tick
+    pub tick: u64,
+    /// The SimRng's state from the moment the game was saved
+    pub rng: SimRng,
+}
+
+/// Where F5 saves to and F9 and the menu load from
+pub fn quicksave_path() -> PathBuf {
+    application_root_dir()
+        .expect("Failed to find the application root")
+        .join("saves")
+        .join("quicksave.ron")
+}
+
+fn translation(transform: &Transform) -> [f32; 3] {
+    let t = transform.translation();
+    [t.x, t.y, t.z]
+}
+
+fn transform_at([x, y, z]: [f32; 3]) -> Transform {
+    let mut transform = Transform::default();
+    transform.set_translation(Vector3::new(x, y, z));
+    transform
+}
+
+impl SaveGame {
+    /// Captures the world between ticks without changing it, so carrying on
+    /// and loading the save play out the same way
+    pub fn capture(world: &World) -> Self {
+        let entities = world.entities();
+        let maps = world.read_storage::<Map>();
+        let transforms = world.read_storage::<Transform>();
+        let velocities = world.read_storage::<Velocity>();
+        let creeps = world.read_storage::<Creep>();
+        let healths = world.read_storage::<Health>();
+        let ages = world.read_storage::<Age>();
+        let navigators = world.read_storage::<Navigator>();
+        let paths = world.read_storage::<Path>();
+        let towers = world.read_storage::<Tower>();
+        let projectiles = world.read_storage::<Projectile>();
+
+        let (map_seed, map_version) = (&entities, &maps).join()
+            .find(|(e, _)| entities.is_alive(*e))
+            .map_or((0, 0), |(_, map)| (map.seed(), map.version()));
+
+        let mut creep_indices = HashMap::new();
+        let mut creep_saves = Vec::new();
+        for (e, creep, health, transform, velocity, age, navigator, path) in (&entities, &creeps, &healths, &transforms, &velocities, &ages, &navigators, paths.maybe()).join() {
+            creep_indices.insert(e, creep_saves.len());
+            creep_saves.push(CreepSave {
+                archetype: creep.archetype.clone(),
+                translation: translation(transform),
+                velocity: [velocity.velocity.x, velocity.velocity.y],
+                speed: velocity.speed,
+                health: *health,
+                age: Age {
+                    age: age.age,
+                    max_age: age.max_age,
+                },
+                navigator: *navigator,
+                path: path.map(|path| PathSave {
+                    objective: path.objective(),
+                    origin: path.origin(),
+                    path: path.path.clone(),
+                    path_i: path.path_i,
+                    start_time: path.start_time,
+                    current: path.map_version == map_version,
+                }),
+            });
+        }
+
+        let tower_saves = (&towers).join().map(|tower| TowerSave {
+            archetype: tower.archetype.clone(),
+            cell: tower.cell,
+            level: tower.level,
+            value: tower.value,
+            targeting: tower.targeting,
+            elapsed: tower.elapsed,
+            target: tower.target.and_then(|target| creep_indices.get(&target).cloned()),
+        }).collect();
+
+        let projectile_saves = (&projectiles, &transforms, &velocities).join().map(|(projectile, transform, velocity)| ProjectileSave {
+            translation: translation(transform),
+            velocity: [velocity.velocity.x, velocity.velocity.y],
+            speed: velocity.speed,
+            projectile: Projectile::new(projectile.damage, projectile.kind),
+        }).collect();
+
+        Self {
+            map: world.read_resource::<MapConfig>().clone(),
+            map_seed,
+            map_version,
+            towers: tower_saves,
+            creeps: creep_saves,
+            projectiles: projectile_saves,
+            gold: *world.read_resource::<Gold>(),
+            lives: *world.read_resource::<Lives>(),
+            stats: world.read_resource::<Stats>().clone(),
+            wave_status: world.read_resource::<WaveStatus>().clone(),
+            wave_progress: world.read_resource::<WaveProgress>().clone(),
+            tick: world.read_resource::<SimClock>().tick,
+            rng: world.read_resource::<SimRng>().clone(),
+        }
+    }
+
+    pub fn write(&self, path: &FilePath) -> Result<(), Error> {
+        let ron = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default()).map_err(Error::Serialize)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(Error::Io)?;
+        }
+        fs::write(path, ron).map_err(Error::Io)
+    }
+
+    pub fn read(path: &FilePath) -> Result<Self, Error> {
+        let ron = fs::read_to_string(path).map_err(Error::Io)?;
+        ron::de::from_str(&ron).map_err(Error::Deserialize)
+    }
+
+    /// Replaces the game in the world with the saved one
+    pub fn restore(self, world: &mut World) {
+        let old: Vec<Entity> = {
+            let entities = world.entities();
+            let maps = world.read_storage::<Map>();
+            let towers = world.read_storage::<Tower>();
+            let creeps = world.read_storage::<Creep>();
+            let projectiles = world.read_storage::<Projectile>();
+            let dying = world.read_storage::<Dying>();
+            let damage_numbers = world.read_storage::<DamageNumber>();
+            let mut old: Vec<Entity> = (&entities, &maps).join().map(|(e, _)| e).collect();
+            old.extend((&entities, &towers).join().map(|(e, _)| e));
+            old.extend((&entities, &creeps).join().map(|(e, _)| e));
+            old.extend((&entities, &projectiles).join().map(|(e, _)| e));
+            old.extend((&entities, &dying).join().map(|(e, _)| e));
+            old.extend((&entities, &damage_numbers).join().map(|(e, _)| e));
+            old
+        };
+        world.delete_entities(&old).expect("Failed to delete entities");
+        world.maintain();
+
+        let mut map = Map::new(self.map.width, self.map.height);
+        map.generate_with_seed(&self.map, self.map_seed);
+        for tower in &self.towers {
+            map.set_occupied(tower.cell, true);
+        }
+        let map_version = map.version();
+        let map_entity = world.create_entity().with(map).build();
+
+        // Spawned with their archetype defaults then patched once they exist
+        let (creep_entities, tower_entities) = {
+            let entities = world.entities();
+            let lazy_update = world.read_resource::<LazyUpdate>();
+            let sprites = world.try_fetch::<Sprites>();
+            let sprites = sprites.as_ref().map(|s| &**s);
+            let creep_config = world.read_resource::<CreepConfig>();
+            let tower_config = world.read_resource::<TowerConfig>();
+            let spawner_config = world.read_resource::<SpawnerConfig>();
+            let mut rng = world.write_resource::<SimRng>();
+
+            let creep_entities: Vec<_> = self.creeps.iter().map(|creep| {
+                let archetype = creep_config.archetype(&creep.archetype)?;
+                let cell = (creep.navigator.x, creep.navigator.y);
+                Some(spawn_creep(&mut rng.rng, &entities, &lazy_update, sprites, cell, archetype, &spawner_config))
+            }).collect();
+
+            let tower_entities: Vec<_> = self.towers.iter().map(|tower| {
+                let archetype = tower_config.archetype(&tower.archetype)?;
+                let entity = spawn_tower(&entities, &lazy_update, sprites, tower.cell, archetype);
+                if let (true, Some(sprites)) = (archetype.sprite(tower.level) != archetype.sprite, sprites) {
+                    if let Some(components) = sprites.get_animated_sprite_components(archetype.sprite(tower.level)) {
+                        components.insert(entity, &lazy_update);
+                    }
+                }
+                Some(entity)
+            }).collect();
+
+            for projectile in self.projectiles {
+                let velocity = Velocity {
+                    velocity: Vector2::new(projectile.velocity[0], projectile.velocity[1]),
+                    speed: projectile.speed,
+                };
+                spawn_projectile(&entities, &lazy_update, transform_at(projectile.translation), velocity, projectile.projectile);
+            }
+
+            (creep_entities, tower_entities)
+        };
+        world.maintain();
+
+        {
+            let mut transforms = world.write_storage::<Transform>();
+            let mut velocities = world.write_storage::<Velocity>();
+            let mut healths = world.write_storage::<Health>();
+            let mut ages = world.write_storage::<Age>();
+            let mut navigators = world.write_storage::<Navigator>();
+            let mut paths = world.write_storage::<Path>();
+
+            for (creep, entity) in self.creeps.into_iter().zip(creep_entities.iter()) {
+                let entity = match entity {
+                    Some(entity) => *entity,
+                    None => {
+                        log::warn!("Not loading creep with unknown archetype {}", creep.archetype);
+                        continue;
+                    },
+                };
+
+                // Only the translation so the archetype's scale is kept
+                if let Some(transform) = transforms.get_mut(entity) {
+                    let [x, y, z] = creep.translation;
+                    transform.set_translation_xyz(x, y, z);
+                }
+                velocities.insert(entity, Velocity {
+                    velocity: Vector2::new(creep.velocity[0], creep.velocity[1]),
+                    speed: creep.speed,
+                }).expect("Failed to insert Velocity");
+                healths.insert(entity, creep.health).expect("Failed to insert Health");
+                ages.insert(entity, creep.age).expect("Failed to insert Age");
+                navigators.insert(entity, creep.navigator).expect("Failed to insert Navigator");
+
+                if let Some(saved) = creep.path {
+                    let mut path = Path::new(map_entity, saved.objective, saved.origin);
+                    path.path = saved.path;
+                    path.path_i = saved.path_i;
+                    path.start_time = saved.start_time;
+                    path.map_version = if saved.current { map_version } else { 0 };
+                    paths.insert(entity, path).expect("Failed to insert Path");
+                }
+            }
+        }
+
+        {
+            let tower_config = world.read_resource::<TowerConfig>();
+            let mut towers = world.write_storage::<Tower>();
+            for (saved, entity) in self.towers.iter().zip(tower_entities.iter()) {
+                let (entity, archetype) = match (entity, tower_config.archetype(&saved.archetype)) {
+                    (Some(entity), Some(archetype)) => (*entity, archetype),
+                    _ => {
+                        log::warn!("Not loading tower with unknown archetype {}", saved.archetype);
+                        continue;
+                    },
+                };
+
+                if let Some(tower) = towers.get_mut(entity) {
+                    let stats = archetype.stats(saved.level);
+                    tower.level = saved.level;
+                    tower.value = saved.value;
+                    tower.range = stats.range;
+                    tower.frequency = stats.frequency;
+                    tower.targeting = saved.targeting;
+                    tower.elapsed = saved.elapsed;
+                    tower.target = saved.target.and_then(|i| creep_entities.get(i).and_then(|e| *e));
+                }
+            }
+        }
+
+        world.write_resource::<SimClock>().tick = self.tick;
+        world.insert(Commands::default());
+        // A replay has to start from the beginning of a game
+        if world.remove::<Recording>().is_some() {
+            log::warn!("Stopped recording the replay because a save was loaded");
+        }
+        world.insert(CommandLog::default());
+        world.insert(Playback::default());
+        world.insert(self.map);
+        world.insert(self.gold);
+        world.insert(self.lives);
+        world.insert(self.stats);
+        world.insert(self.wave_status);
+        world.insert(self.wave_progress);
+        // Spawning the entities above drew from the live rng, the saved state replaces it
+        world.insert(self.rng);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,14 @@
+use amethyst::core::math::{
+    Vector2,
+    Vector3,
+};
+
+/// Converts a position in window pixels into world space. This mirrors the orthographic
+/// projection MoveCamera sets up, centred on the camera and scaled by zoom
+pub fn screen_to_world(screen: Vector2<f32>, screen_size: Vector2<f32>, camera: &Vector3<f32>, zoom: f32) -> Vector2<f32> {
+    // Window y goes down the screen but world y goes up
+    Vector2::new(
+        camera.x + (screen.x - screen_size.x * 0.5) * zoom,
+        camera.y - (screen.y - screen_size.y * 0.5) * zoom,
+    )
+}
\ No newline at end of file
@@ -0,0 +1,90 @@
+use amethyst::{
+    core::{
+        transform::Transform,
+        ArcThreadPool,
+        SystemBundle,
+    },
+    ecs::prelude::{
+        Dispatcher,
+        DispatcherBuilder,
+        Join,
+        World,
+        WorldExt,
+    },
+};
+use crate::{
+    components::{
+        TickPosition,
+        Velocity,
+    },
+    resources::SimClock,
+    systems::SimulationBundle,
+};
+
+/// Dispatches the gameplay systems one SimClock tick at a time
+pub struct Simulation {
+    dispatcher: Dispatcher<'static, 'static>,
+}
+
+impl Simulation {
+    pub fn new(world: &mut World) -> Self {
+        let mut builder = DispatcherBuilder::new();
+        SimulationBundle
+            .build(world, &mut builder)
+            .expect("Failed to build simulation systems");
+
+        let pool = world.read_resource::<ArcThreadPool>().clone();
+        let mut dispatcher = builder.with_pool(pool).build();
+        dispatcher.setup(world);
+
+        Self {
+            dispatcher,
+        }
+    }
+
+    pub fn tick(&mut self, world: &mut World) {
+        world.write_resource::<SimClock>().advance();
+        self.dispatcher.dispatch(world);
+        // Entities created or deleted this tick exist for the next one
+        world.maintain();
+    }
+}
+
+/// Puts moving entities back where the last tick left them so the simulation and
+/// saves never see a blended position, and starts blending from there
+pub fn restore_tick_positions(world: &mut World) {
+    let mut transforms = world.write_storage::<Transform>();
+    let mut tick_positions = world.write_storage::<TickPosition>();
+    for (transform, tick_position) in (&mut transforms, &mut tick_positions).join() {
+        transform.set_translation(tick_position.current);
+        tick_position.previous = tick_position.current;
+    }
+}
+
+/// Records where moving entities ended up after a fixed update's ticks, anything
+/// that started moving since the last one starts without blending
+pub fn record_tick_positions(world: &mut World) {
+    let entities = world.entities();
+    let transforms = world.read_storage::<Transform>();
+    let velocities = world.read_storage::<Velocity>();
+    let mut tick_positions = world.write_storage::<TickPosition>();
+    for (e, transform, _) in (&entities, &transforms, &velocities).join() {
+        let position = *transform.translation();
+        match tick_positions.get_mut(e) {
+            Some(tick_position) => tick_position.current = position,
+            None => {
+                tick_positions.insert(e, TickPosition::new(position)).expect("Failed to insert TickPosition component");
+            },
+        }
+    }
+}
+
+/// Moves entities alpha of the way from their position before the last fixed
+/// update to the one after it, alpha being how far into the next fixed step the frame is
+pub fn blend_tick_positions(world: &mut World, alpha: f32) {
+    let mut transforms = world.write_storage::<Transform>();
+    let tick_positions = world.read_storage::<TickPosition>();
+    for (transform, tick_position) in (&mut transforms, &tick_positions).join() {
+        transform.set_translation(tick_position.blend(alpha));
+    }
+}
\ No newline at end of file
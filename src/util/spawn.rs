@@ -1,10 +1,12 @@
 use amethyst::{
     core::{
         transform::Transform,
-        math::Vector2,
+        math::{
+            Vector2,
+            Vector3,
+        },
     },
     ecs::prelude::{
-        Read, 
         Entities, 
         LazyUpdate,
         Builder,
@@ -23,69 +25,72 @@ use crate::{
         Navigator,
         Map,
         Creep,
-        BulletTower,
+        Health,
         Tower,
-        map::MapObject,
         Projectile,
+        DamageNumber,
     },
     resources::Sprites,
     util::{
-        constants::CHARACTER_Z_OFFSET,
+        constants::{
+            CHARACTER_Z_OFFSET,
+            HUD_Z,
+        },
         iso_to_screen,
     },
     config::{
         Spawner as SpawnerConfig,
-        Tower as TowerConfig,
+        tower::TowerArchetype,
+        creep::CreepArchetype,
     },
 };
 use rand::{
-    thread_rng,
+    Rng,
     seq::SliceRandom,
 };
 
 #[derive(Debug)]
 pub enum Error {
-    MapHasNoRooms,
+    MapHasNoEntrances,
 }
 
-pub fn random_room(map: &Map) -> Result<&MapObject, Error> {
+pub fn random_entrance<R: Rng>(rng: &mut R, map: &Map) -> Result<(usize, usize), Error> {
     map
-        .rooms()
-        .choose(&mut thread_rng())
-        .ok_or(Error::MapHasNoRooms)
+        .entrances()
+        .choose(rng)
+        .cloned()
+        .ok_or(Error::MapHasNoEntrances)
 }
 
-pub fn spawn_creep(
+pub fn spawn_creep<R: Rng>(
+    rng: &mut R,
     entities: &Entities,
     lazy_update: &LazyUpdate,
-    sprites_resource: &Read<Sprites>,
-    room: &MapObject,
+    sprites_resource: Option<&Sprites>,
+    (x, y): (usize, usize),
+    archetype: &CreepArchetype,
     spawner_config: &SpawnerConfig,
 ) -> Entity {
-    let centre = room.centre();
-    let map_pos = Vector2::new(centre.x, centre.z);
+    let map_pos = Vector2::new(x as f32, y as f32);
     let transform = {
         let mut screen_pos = iso_to_screen(map_pos);
         screen_pos.z += CHARACTER_Z_OFFSET;
         
         let mut transform = Transform::default();
         transform.set_translation(screen_pos);
+        transform.set_scale(Vector3::new(archetype.size, archetype.size, 1.));
         transform
     };
-    let sprite_components = sprites_resource.get_character_1_components();
-    
-    let navigator = Navigator {
-        x: map_pos.x as usize,
-        y: map_pos.y as usize,
-    };
+    let navigator = Navigator::new(x, y);
     
     let mut builder = lazy_update
         .create_entity(entities)
         .with(Animation::default())
         .with(Transparent)
         .with(transform)
-        .with(Creep)
-        .with(Velocity::rand(spawner_config.min_speed, spawner_config.max_speed))
+        .with(Creep::new(archetype))
+        .with(Health::new(archetype.health))
+        .with(Velocity::rand(rng, archetype.min_speed(), archetype.max_speed()))
         .with(Age {
             age: 0.,
             max_age: spawner_config.max_age,
@@ -93,21 +98,27 @@ pub fn spawn_creep(
         .with(navigator)
         .with(DebugLinesComponent::new());
 
-
-    builder = sprite_components.apply(builder);
+    // Headless simulations have no sprites
+    if let Some(sprites_resource) = sprites_resource {
+        let sprite_components = sprites_resource
+            .get_animated_sprite_components(&archetype.sprite)
+            .unwrap_or_else(|| {
+                log::warn!("Creep archetype {} has unknown sprite {}", archetype.name, archetype.sprite);
+                sprites_resource.get_default_character_components()
+            });
+        builder = sprite_components.apply(builder);
+    }
     builder.build()
 }
 
 pub fn spawn_tower(
     entities: &Entities,
     lazy_update: &LazyUpdate,
-    sprites_resource: &Read<Sprites>,
-    room: &MapObject,
-    tower_config: &TowerConfig,
+    sprites_resource: Option<&Sprites>,
+    (x, y): (usize, usize),
+    archetype: &TowerArchetype,
 ) -> Entity {
-    let pos = room.pos();
-    let size = room.size();
-    let map_pos = Vector2::new(pos.x + size.x - 1., pos.z + size.z - 1.);
+    let map_pos = Vector2::new(x as f32, y as f32);
     let transform = {
         let mut screen_pos = iso_to_screen(map_pos);
         screen_pos.z += CHARACTER_Z_OFFSET;
@@ -116,19 +127,23 @@ pub fn spawn_tower(
         transform.set_translation(screen_pos);
         transform
     };
-    let sprite_components = sprites_resource.get_character_1_components();
-
     let mut builder = lazy_update
         .create_entity(entities)
         .with(Animation::default())
         .with(Transparent)
         .with(transform)
-        .with(Tower::new(tower_config.range, tower_config.frequency))
-        .with(BulletTower::default())
+        .with(Tower::new(archetype, (x, y)))
         .with(DebugLinesComponent::new());
-   
-            
-    builder = sprite_components.apply(builder);
+
+    if let Some(sprites_resource) = sprites_resource {
+        let sprite_components = sprites_resource
+            .get_animated_sprite_components(&archetype.sprite)
+            .unwrap_or_else(|| {
+                log::warn!("Tower archetype {} has unknown sprite {}", archetype.name, archetype.sprite);
+                sprites_resource.get_default_character_components()
+            });
+        builder = sprite_components.apply(builder);
+    }
     builder.build()
 }
 
@@ -137,14 +152,39 @@ pub fn spawn_projectile(
     lazy_update: &LazyUpdate,
     transform: Transform,
     velocity: Velocity,
+    projectile: Projectile,
 ) -> Entity {
     lazy_update
         .create_entity(entities)
         //.with(Animation::default())
         .with(Transparent)
         .with(transform)
-        .with(Projectile)
+        .with(projectile)
         .with(velocity)
         .with(DebugLinesComponent::new())
         .build()
+}
+
+/// Seconds a damage number floats for
+const DAMAGE_NUMBER_SECONDS: f32 = 0.8;
+
+pub fn spawn_damage_number(
+    entities: &Entities,
+    lazy_update: &LazyUpdate,
+    position: &Vector3<f32>,
+    damage: f32,
+) -> Entity {
+    let mut transform = Transform::default();
+    transform.set_translation_xyz(position.x, position.y, HUD_Z);
+
+    lazy_update
+        .create_entity(entities)
+        .with(transform)
+        .with(DamageNumber::new(damage))
+        .with(Age {
+            age: 0.,
+            max_age: Some(DAMAGE_NUMBER_SECONDS),
+        })
+        .with(DebugLinesComponent::new())
+        .build()
 }
\ No newline at end of file